@@ -0,0 +1,93 @@
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Stops a solver that's taking too long. A token is installed on the thread
+/// running the solver, and loops that might run for a long time call `check`,
+/// which fails once the token has been cancelled or its time is up. Solvers
+/// that never call `check` can't be stopped.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    timeout: Option<Duration>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+impl CancelToken {
+    /// A token that's cancelled automatically once `timeout` has passed.
+    pub fn with_timeout(timeout: Duration) -> CancelToken {
+        CancelToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            deadline: Some(Instant::now() + timeout),
+            timeout: Some(timeout),
+        }
+    }
+
+    /// Cancels the token, including its clones on other threads.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn check(&self) -> Result<()> {
+        let timed_out = self.deadline.is_some_and(|d| Instant::now() >= d);
+        if timed_out || self.cancelled.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled {
+                part: None,
+                timeout: if timed_out { self.timeout } else { None },
+            });
+        }
+        Ok(())
+    }
+
+    /// Runs `f` with this token installed on the current thread, putting back
+    /// whichever token was there before once it returns or panics.
+    pub fn install<T, F: FnOnce() -> T>(&self, f: F) -> T {
+        struct Restore(Option<CancelToken>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+        let previous = CURRENT.with(|current| current.borrow_mut().replace(self.clone()));
+        let _restore = Restore(previous);
+        f()
+    }
+}
+
+/// Fails if the token installed on this thread has been cancelled or has run
+/// out of time. Without a token it always succeeds.
+pub fn check() -> Result<()> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(token) => token.check(),
+        None => Ok(()),
+    })
+}
+
+#[test]
+fn test_check() {
+    assert!(check().is_ok());
+
+    let token = CancelToken::default();
+    token.install(|| {
+        assert!(check().is_ok());
+        token.cancel();
+        assert_eq!(check().unwrap_err().to_string(), "cancelled");
+    });
+    assert!(check().is_ok());
+}
+
+#[test]
+fn test_timeout() {
+    let token = CancelToken::with_timeout(Duration::from_millis(0));
+    let error = token.install(check).unwrap_err();
+    assert_eq!(error.in_part(2).to_string(), "part 2 timed out after 0ns");
+
+    let token = CancelToken::with_timeout(Duration::from_secs(60));
+    assert!(token.install(check).is_ok());
+}
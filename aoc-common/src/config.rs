@@ -0,0 +1,214 @@
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+/// Parameters for a day's solver, such as the target sum or the number of
+/// turns to play. They're read from `name = value` lines in a day's
+/// `config.txt`, where blank lines and lines starting with `#` are ignored,
+/// and can be overridden on the command line with `--set name=value`.
+#[derive(Debug, Default)]
+pub struct Config {
+    entries: Vec<Entry>,
+    // Names that a solver has asked for, so misspelled ones can be reported
+    used: RefCell<HashSet<String>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    value: String,
+    // Where the entry came from, for error messages
+    source: String,
+    line: usize,
+    value_column: usize,
+}
+
+impl Config {
+    /// Reads a config file, treating a missing file as an empty config.
+    pub fn read(filename: &str) -> Result<Config> {
+        match fs::read_to_string(filename) {
+            Ok(contents) => Config::parse(filename, &contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(source) => Err(Error::Io {
+                file: Some(String::from(filename)),
+                source,
+            }),
+        }
+    }
+
+    pub fn parse(source: &str, contents: &str) -> Result<Config> {
+        let mut config = Config::default();
+        for (i, line) in contents.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            config
+                .add(source, i + 1, line)
+                .map_err(|e| e.in_file(source))?;
+        }
+        Ok(config)
+    }
+
+    /// Adds a `name=value` override, which takes precedence over the file.
+    pub fn set(&mut self, assignment: &str) -> Result<()> {
+        self.add("--set", 1, assignment)
+            .map_err(|e| e.in_file("--set"))
+    }
+
+    fn add(&mut self, source: &str, line: usize, text: &str) -> Result<()> {
+        let equals = match text.find('=') {
+            Some(equals) => equals,
+            None => return Err(Error::parse(line, text, text, "name = value")),
+        };
+        let name = text[..equals].trim();
+        if name.is_empty() {
+            return Err(Error::parse(line, text, text, "name = value"));
+        }
+        let value = text[equals + 1..].trim();
+        let value_start = text.len() - text[equals + 1..].trim_start().len();
+        let value_column = text[..value_start].chars().count() + 1;
+        self.entries.push(Entry {
+            name: String::from(name),
+            value: String::from(value),
+            source: String::from(source),
+            line,
+            value_column,
+        });
+        Ok(())
+    }
+
+    /// Looks up a parameter, using the last value given for it.
+    pub fn get_opt<T: FromStr>(&self, name: &str, expected: &str) -> Result<Option<T>> {
+        self.used.borrow_mut().insert(String::from(name));
+        match self.entries.iter().rev().find(|e| e.name == name) {
+            Some(entry) => entry.value.parse().map(Some).map_err(|_| Error::Parse {
+                file: Some(entry.source.clone()),
+                line: entry.line,
+                column: entry.value_column,
+                text: entry.value.clone(),
+                expected: format!("{} for {}", expected, name),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str, default: T, expected: &str) -> Result<T> {
+        Ok(self.get_opt(name, expected)?.unwrap_or(default))
+    }
+
+    /// Looks up a parameter holding a whitespace-separated list.
+    pub fn get_list<T: FromStr>(
+        &self,
+        name: &str,
+        default: Vec<T>,
+        expected: &str,
+    ) -> Result<Vec<T>> {
+        let list: Option<String> = self.get_opt(name, expected)?;
+        let list = match list {
+            Some(list) => list,
+            None => return Ok(default),
+        };
+        let entry = self.entries.iter().rev().find(|e| e.name == name).unwrap();
+        let mut values = Vec::new();
+        for item in list.split_whitespace() {
+            let offset = item.as_ptr() as usize - list.as_ptr() as usize;
+            values.push(item.parse().map_err(|_| Error::Parse {
+                file: Some(entry.source.clone()),
+                line: entry.line,
+                column: entry.value_column + list[..offset].chars().count(),
+                text: String::from(item),
+                expected: format!("{} for {}", expected, name),
+            })?);
+        }
+        Ok(values)
+    }
+
+    /// Fails on the first parameter that no solver has looked up, which is
+    /// most likely misspelled or meant for a different day.
+    pub fn check_all_used(&self) -> Result<()> {
+        let used = self.used.borrow();
+        match self.entries.iter().find(|e| !used.contains(&e.name)) {
+            Some(entry) => Err(Error::Parse {
+                file: Some(entry.source.clone()),
+                line: entry.line,
+                column: 1,
+                text: entry.name.clone(),
+                expected: String::from("a parameter this day uses"),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_parse_config() {
+    let mut config = Config::parse(
+        "config.txt",
+        "# Day 9\npreamble = 25\n\nname=shiny gold\nslopes = 1,1 3,1\n",
+    )
+    .unwrap();
+    assert_eq!(config.get("preamble", 0, "a number").unwrap(), 25);
+    assert_eq!(config.get("turns", 7, "a number").unwrap(), 7);
+    assert_eq!(
+        config.get("name", String::new(), "a name").unwrap(),
+        "shiny gold"
+    );
+    assert_eq!(
+        config
+            .get_list::<String>("slopes", vec![], "a slope")
+            .unwrap(),
+        vec![String::from("1,1"), String::from("3,1")]
+    );
+
+    config.set("preamble=5").unwrap();
+    assert_eq!(config.get("preamble", 0, "a number").unwrap(), 5);
+}
+
+#[test]
+fn test_config_errors() {
+    assert_eq!(
+        Config::parse("config.txt", "preamble 25")
+            .unwrap_err()
+            .to_string(),
+        "config.txt:1:1: expected name = value, found \"preamble 25\""
+    );
+
+    let config = Config::parse("config.txt", "\npreamble =  2x\nlist = 1 2 x").unwrap();
+    assert_eq!(
+        config
+            .get("preamble", 0, "a number")
+            .unwrap_err()
+            .to_string(),
+        "config.txt:2:13: expected a number for preamble, found \"2x\""
+    );
+    assert_eq!(
+        config
+            .get_list::<u32>("list", vec![], "a number")
+            .unwrap_err()
+            .to_string(),
+        "config.txt:3:12: expected a number for list, found \"x\""
+    );
+}
+
+#[test]
+fn test_check_all_used() {
+    let mut config = Config::default();
+    config.set("preamble=5").unwrap();
+    config.set("preambel=5").unwrap();
+    config.get("preamble", 25, "a number").unwrap();
+    assert_eq!(
+        config.check_all_used().unwrap_err().to_string(),
+        "--set:1:1: expected a parameter this day uses, found \"preambel\""
+    );
+}
+
+#[test]
+fn test_missing_config_is_empty() {
+    let config = Config::read("no_such_config.txt").unwrap();
+    assert_eq!(config.get("target", 2020, "a number").unwrap(), 2020);
+    assert!(config.check_all_used().is_ok());
+}
@@ -0,0 +1,178 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input couldn't be read.
+    Io {
+        file: Option<String>,
+        source: io::Error,
+    },
+    /// Part of the input isn't in the expected format. `line` and `column`
+    /// start at 1, and `text` is the piece of input that was rejected.
+    Parse {
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        text: String,
+        expected: String,
+    },
+    /// The input is well formed, but doesn't have an answer.
+    NoAnswer(String),
+    /// Working out an answer overflowed the integer type it's computed in.
+    Overflow(String),
+    /// The solver was stopped by its `CancelToken`. `timeout` is set when it
+    /// ran out of time, and `part` once the part it was solving is known.
+    Cancelled {
+        part: Option<u32>,
+        timeout: Option<Duration>,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// A parse error for `text` on the `line`th line of input. When `text` is
+    /// a slice of `line_text` its column is worked out from its position.
+    pub fn parse(line: usize, line_text: &str, text: &str, expected: &str) -> Error {
+        Error::Parse {
+            file: None,
+            line,
+            column: column_of(line_text, text),
+            text: String::from(text),
+            expected: String::from(expected),
+        }
+    }
+
+    /// Records the file the error came from, unless it is already known.
+    pub fn in_file(self, filename: &str) -> Error {
+        match self {
+            Error::Io { file: None, source } => Error::Io {
+                file: Some(String::from(filename)),
+                source,
+            },
+            Error::Parse {
+                file: None,
+                line,
+                column,
+                text,
+                expected,
+            } => Error::Parse {
+                file: Some(String::from(filename)),
+                line,
+                column,
+                text,
+                expected,
+            },
+            other => other,
+        }
+    }
+
+    /// Records the part being solved when the error happened, for errors
+    /// where that matters.
+    pub fn in_part(self, part: u32) -> Error {
+        match self {
+            Error::Cancelled {
+                part: None,
+                timeout,
+            } => Error::Cancelled {
+                part: Some(part),
+                timeout,
+            },
+            other => other,
+        }
+    }
+}
+
+/// Parses `text`, a piece of the `line`th line of input, reporting where it
+/// was if it isn't valid.
+pub fn parse_at<T: FromStr>(line: usize, line_text: &str, text: &str, expected: &str) -> Result<T> {
+    text.parse()
+        .map_err(|_| Error::parse(line, line_text, text, expected))
+}
+
+fn column_of(line_text: &str, text: &str) -> usize {
+    let line_start = line_text.as_ptr() as usize;
+    let text_start = text.as_ptr() as usize;
+    if text_start >= line_start && text_start + text.len() <= line_start + line_text.len() {
+        line_text[..text_start - line_start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { file, source } => write!(
+                f,
+                "couldn't read {}: {}",
+                file.as_deref().unwrap_or("<input>"),
+                source
+            ),
+            Error::Parse {
+                file,
+                line,
+                column,
+                text,
+                expected,
+            } => write!(
+                f,
+                "{}:{}:{}: expected {}, found {:?}",
+                file.as_deref().unwrap_or("<input>"),
+                line,
+                column,
+                expected,
+                text
+            ),
+            Error::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+            Error::Overflow(what) => write!(f, "overflow: {}", what),
+            Error::Cancelled { part, timeout } => {
+                if let Some(part) = part {
+                    write!(f, "part {} ", part)?;
+                }
+                match (part, timeout) {
+                    (_, Some(timeout)) => write!(f, "timed out after {:?}", timeout),
+                    (Some(_), None) => write!(f, "was cancelled"),
+                    (None, None) => write!(f, "cancelled"),
+                }
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_parse_error_column() {
+    let line = "mem[8] = 11x";
+    let error = parse_at::<u64>(3, line, &line[9..], "a number").unwrap_err();
+    assert_eq!(
+        error.in_file("input.txt").to_string(),
+        "input.txt:3:10: expected a number, found \"11x\""
+    );
+
+    let error = Error::parse(1, line, "not from the line", "something");
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:1: expected something, found \"not from the line\""
+    );
+}
+
+#[test]
+fn test_in_file_keeps_existing_file() {
+    let error = Error::parse(2, "abc", "abc", "a number")
+        .in_file("first.txt")
+        .in_file("second.txt");
+    assert!(error.to_string().starts_with("first.txt:2:1"));
+}
@@ -0,0 +1,18 @@
+/// Offsets an index by a signed amount, returning `None` if the result would
+/// fall outside the range of `usize`.
+pub fn add(u: usize, i: i32) -> Option<usize> {
+    if i.is_negative() {
+        u.checked_sub(i.wrapping_abs() as u32 as usize)
+    } else {
+        u.checked_add(i as usize)
+    }
+}
+
+#[test]
+fn test_add() {
+    assert_eq!(add(0, 1), Some(1));
+    assert_eq!(add(5, -5), Some(0));
+    assert!(add(0, -1).is_none());
+    assert_eq!(add(1, i32::MIN), None);
+    assert!(add(usize::MAX, 1).is_none());
+}
@@ -0,0 +1,133 @@
+use crate::error::{parse_at, Error, Result};
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A block of consecutive non-blank lines, along with the line number (from 1)
+/// of its first line.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Record {
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+
+/// Opens a file for reading, or stdin when `filename` is `-`.
+pub fn open(filename: &str) -> Result<Box<dyn BufRead>> {
+    crate::info!("Reading file {}", input_name(filename));
+    if filename == "-" {
+        return Ok(Box::new(io::BufReader::new(io::stdin())));
+    }
+    let file = fs::File::open(filename).map_err(|source| Error::Io {
+        file: Some(String::from(filename)),
+        source,
+    })?;
+    Ok(Box::new(io::BufReader::new(file)))
+}
+
+/// The name to show for a file in messages, since `-` means stdin.
+pub fn input_name(filename: &str) -> &str {
+    if filename == "-" {
+        "<stdin>"
+    } else {
+        filename
+    }
+}
+
+/// Reads every line of the input.
+pub fn read_lines<R: BufRead>(input: R) -> Result<Vec<String>> {
+    let mut parsed_lines = Vec::new();
+    for line in input.lines() {
+        parsed_lines.push(line.map_err(|source| Error::Io { file: None, source })?);
+    }
+    Ok(parsed_lines)
+}
+
+/// Reads input containing one number per line.
+pub fn read_numbers<T: FromStr, R: BufRead>(input: R) -> Result<Vec<T>> {
+    to_numbers(&read_lines(input)?)
+}
+
+/// Reads input as a grid of characters, one row per line.
+pub fn read_char_grid<R: BufRead>(input: R) -> Result<Vec<Vec<char>>> {
+    Ok(read_lines(input)?
+        .iter()
+        .map(|line| line.chars().collect())
+        .collect())
+}
+
+/// Reads input made of blocks of lines separated by blank lines.
+pub fn read_records<R: BufRead>(input: R) -> Result<Vec<Record>> {
+    Ok(to_records(read_lines(input)?))
+}
+
+fn to_numbers<T: FromStr>(lines: &[String]) -> Result<Vec<T>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse_at(i + 1, line, line, "a number"))
+        .collect()
+}
+
+fn to_records(lines: Vec<String>) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut record = Record {
+        line: 1,
+        lines: Vec::new(),
+    };
+    for (i, line) in lines.into_iter().enumerate() {
+        if line.is_empty() {
+            records.push(record);
+            record = Record {
+                line: i + 2,
+                lines: Vec::new(),
+            };
+        } else {
+            record.lines.push(line);
+        }
+    }
+    records.push(record);
+    records
+}
+
+#[test]
+fn test_read_records() {
+    let records = read_records("a\nb\n\nc\n\nd\ne".as_bytes()).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].lines, vec!["a", "b"]);
+    assert_eq!(records[1].lines, vec!["c"]);
+    assert_eq!(records[2].lines, vec!["d", "e"]);
+    assert_eq!(records[2].line, 6);
+}
+
+#[test]
+fn test_read_numbers() {
+    let numbers: Vec<u32> = read_numbers("1\n22\n".as_bytes()).unwrap();
+    assert_eq!(numbers, vec![1, 22]);
+
+    let error = read_numbers::<u32, _>("1\n2x".as_bytes()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:2:1: expected a number, found \"2x\""
+    );
+}
+
+#[test]
+fn test_read_char_grid() {
+    let grid = read_char_grid("#.\n.#".as_bytes()).unwrap();
+    assert_eq!(grid, vec![vec!['#', '.'], vec!['.', '#']]);
+}
+
+#[test]
+fn test_missing_file() {
+    let error = open("no_such_file.txt").err().unwrap();
+    assert!(error
+        .to_string()
+        .starts_with("couldn't read no_such_file.txt"));
+}
+
+#[test]
+fn test_input_name() {
+    assert_eq!(input_name("-"), "<stdin>");
+    assert_eq!(input_name("input.txt"), "input.txt");
+}
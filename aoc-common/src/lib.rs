@@ -0,0 +1,23 @@
+//! Helpers shared by every day's solution.
+
+pub mod alloc;
+pub mod cancel;
+pub mod config;
+pub mod error;
+pub mod index;
+pub mod input;
+pub mod log;
+pub mod output;
+pub mod random;
+pub mod run;
+pub mod solution;
+
+pub use cancel::CancelToken;
+pub use config::Config;
+pub use error::{parse_at, Error, Result};
+pub use index::add;
+pub use input::{open, read_char_grid, read_lines, read_numbers, read_records, Record};
+pub use output::{format_report, json_string, Format};
+pub use random::Rng;
+pub use run::run_day;
+pub use solution::{PartAnswer, Report, Solution, Solver};
@@ -0,0 +1,216 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// How much gets logged. Messages are logged at `Info`, `Debug` or `Trace`,
+/// and shown when their level is at or below the threshold for their target.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Off,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    fn from_u8(level: u8) -> Level {
+        match level {
+            0 => Level::Off,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Off => "off",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The name of the environment variable holding per-target levels, such as
+/// `day11=trace,day7`. A target without a level gets `trace`.
+pub const TARGETS_VAR: &str = "AOC_LOG";
+
+// The threshold for targets not named in AOC_LOG
+static DEFAULT_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+// The highest threshold of any target, so most disabled messages are rejected
+// without taking the lock
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+static TARGETS: RwLock<Vec<(String, Level)>> = RwLock::new(Vec::new());
+
+/// Sets the default threshold from the command line's verbosity, and the
+/// per-target ones from `AOC_LOG`.
+pub fn init(verbosity: Level) {
+    let targets = match std::env::var(TARGETS_VAR) {
+        Ok(spec) => parse_targets(&spec).unwrap_or_else(|message| {
+            eprintln!("warning: ignoring {}: {}", TARGETS_VAR, message);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    set_levels(verbosity, targets);
+}
+
+pub fn set_levels(default: Level, targets: Vec<(String, Level)>) {
+    let max = targets
+        .iter()
+        .map(|(_, level)| *level)
+        .fold(default, Level::max);
+    DEFAULT_LEVEL.store(default as u8, Ordering::Relaxed);
+    MAX_LEVEL.store(max as u8, Ordering::Relaxed);
+    *TARGETS.write().unwrap() = targets;
+}
+
+/// Parses a comma-separated list of `target` or `target=level` entries.
+pub fn parse_targets(spec: &str) -> Result<Vec<(String, Level)>, String> {
+    let mut targets = Vec::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (target, level) = match entry.split_once('=') {
+            Some((target, level)) => (target, parse_level(level)?),
+            None => (entry, Level::Trace),
+        };
+        targets.push((String::from(target), level));
+    }
+    Ok(targets)
+}
+
+fn parse_level(level: &str) -> Result<Level, String> {
+    match level {
+        "off" => Ok(Level::Off),
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        "trace" => Ok(Level::Trace),
+        _ => Err(format!(
+            "unknown level '{}', expected off, info, debug or trace",
+            level
+        )),
+    }
+}
+
+/// Applies a `-q`, `-v` or `-vv` flag to the verbosity so far, returning
+/// `None` for any other argument.
+pub fn verbosity_flag(arg: &str, verbosity: Level) -> Option<Level> {
+    match arg {
+        "-q" | "--quiet" => Some(Level::Off),
+        "-v" | "--verbose" => Some(Level::from_u8(verbosity as u8 + 1)),
+        "-vv" => Some(Level::Trace),
+        _ => None,
+    }
+}
+
+/// The threshold for a target: that of the last entry naming it or one of
+/// its parent modules, or the default.
+fn threshold(targets: &[(String, Level)], default: Level, target: &str) -> Level {
+    targets
+        .iter()
+        .rev()
+        .find(|(name, _)| {
+            target == name
+                || (target.starts_with(name.as_str()) && target[name.len()..].starts_with("::"))
+        })
+        .map_or(default, |(_, level)| *level)
+}
+
+pub fn enabled(level: Level, target: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+    let default = Level::from_u8(DEFAULT_LEVEL.load(Ordering::Relaxed));
+    level <= threshold(&TARGETS.read().unwrap(), default, target)
+}
+
+/// Writes a message to stderr if it's enabled. Use the `info!`, `debug!` and
+/// `trace!` macros instead, which fill in the calling module as the target.
+pub fn log(level: Level, target: &str, args: fmt::Arguments<'_>) {
+    if !enabled(level, target) {
+        return;
+    }
+    match level {
+        Level::Info => eprintln!("{}", args),
+        _ => eprintln!("[{} {}] {}", level, target, args),
+    }
+}
+
+/// Logs a progress message, shown unless `-q` is given.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Info, module_path!(), format_args!($($arg)*))
+    };
+}
+
+/// Logs a problem that doesn't stop the solver, shown unless `-q` is given.
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::log::log(
+            $crate::log::Level::Info,
+            module_path!(),
+            format_args!("warning: {}", format_args!($($arg)*)),
+        )
+    };
+}
+
+/// Logs a message shown with `-v`.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Debug, module_path!(), format_args!($($arg)*))
+    };
+}
+
+/// Logs a message shown with `-vv`, for tracing a solver step by step.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::Trace, module_path!(), format_args!($($arg)*))
+    };
+}
+
+#[test]
+fn test_parse_targets() {
+    assert_eq!(
+        parse_targets("day11=debug, day7,aoc_common::input=off").unwrap(),
+        vec![
+            (String::from("day11"), Level::Debug),
+            (String::from("day7"), Level::Trace),
+            (String::from("aoc_common::input"), Level::Off),
+        ]
+    );
+    assert_eq!(parse_targets("").unwrap(), vec![]);
+    assert!(parse_targets("day11=loud").is_err());
+}
+
+#[test]
+fn test_threshold() {
+    let targets = parse_targets("day1=debug,aoc_common=off,day11").unwrap();
+    assert_eq!(threshold(&targets, Level::Info, "day1"), Level::Debug);
+    assert_eq!(threshold(&targets, Level::Info, "day11"), Level::Trace);
+    assert_eq!(
+        threshold(&targets, Level::Info, "day11::seats"),
+        Level::Trace
+    );
+    assert_eq!(
+        threshold(&targets, Level::Info, "aoc_common::input"),
+        Level::Off
+    );
+    assert_eq!(threshold(&targets, Level::Info, "day15"), Level::Info);
+}
+
+#[test]
+fn test_verbosity_flag() {
+    assert_eq!(verbosity_flag("-v", Level::Info), Some(Level::Debug));
+    assert_eq!(verbosity_flag("-v", Level::Debug), Some(Level::Trace));
+    assert_eq!(verbosity_flag("-v", Level::Trace), Some(Level::Trace));
+    assert_eq!(verbosity_flag("-vv", Level::Info), Some(Level::Trace));
+    assert_eq!(verbosity_flag("-q", Level::Debug), Some(Level::Off));
+    assert_eq!(verbosity_flag("-x", Level::Info), None);
+}
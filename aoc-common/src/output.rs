@@ -0,0 +1,152 @@
+use crate::solution::Report;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How answers are printed. Diagnostics always go to stderr, so stdout only
+/// ever holds answers in the chosen format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// `PART n: answer` lines followed by the total time.
+    Text,
+    /// One JSON object per line for each part that was solved.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}', expected text or json", s)),
+        }
+    }
+}
+
+/// Formats a day's report, ending with a newline.
+pub fn format_report(day: u32, report: &Report, format: Format) -> String {
+    match format {
+        Format::Text => report.to_string(),
+        Format::Json => {
+            let mut output = String::new();
+            for answer in &report.answers {
+                write!(
+                    output,
+                    "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ms\":{},\"solve_ms\":{}",
+                    day,
+                    answer.part,
+                    json_string(&answer.answer),
+                    millis(report.parse_time),
+                    millis(answer.elapsed)
+                )
+                .unwrap();
+                if let (Some(parse), Some(solve)) = (report.parse_memory, answer.memory) {
+                    write!(
+                        output,
+                        ",\"parse_peak_bytes\":{},\"allocations\":{},\"allocated_bytes\":{},\"peak_bytes\":{}",
+                        parse.peak_bytes, solve.allocations, solve.allocated_bytes, solve.peak_bytes
+                    )
+                    .unwrap();
+                }
+                output.push_str("}\n");
+            }
+            output
+        }
+    }
+}
+
+// Milliseconds with microsecond precision, which is always a valid JSON number
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// Quotes a string for JSON.
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+use crate::solution::PartAnswer;
+
+#[test]
+fn test_format_json() {
+    let report = Report {
+        parse_time: Duration::from_micros(1500),
+        parse_memory: None,
+        answers: vec![
+            PartAnswer {
+                part: 1,
+                answer: String::from("42"),
+                elapsed: Duration::from_micros(250),
+                memory: None,
+            },
+            PartAnswer {
+                part: 2,
+                answer: String::from("a \"b\"\n"),
+                elapsed: Duration::from_secs(2),
+                memory: None,
+            },
+        ],
+    };
+    assert_eq!(
+        format_report(7, &report, Format::Json),
+        "{\"day\":7,\"part\":1,\"answer\":\"42\",\"parse_ms\":1.500,\"solve_ms\":0.250}\n\
+         {\"day\":7,\"part\":2,\"answer\":\"a \\\"b\\\"\\n\",\"parse_ms\":1.500,\"solve_ms\":2000.000}\n"
+    );
+}
+
+#[test]
+fn test_format_json_memory() {
+    use crate::alloc::AllocStats;
+    let stats = |peak_bytes| {
+        Some(AllocStats {
+            allocations: 3,
+            allocated_bytes: 4096,
+            peak_bytes,
+        })
+    };
+    let report = Report {
+        parse_time: Duration::from_millis(1),
+        parse_memory: stats(100),
+        answers: vec![PartAnswer {
+            part: 1,
+            answer: String::from("42"),
+            elapsed: Duration::from_millis(2),
+            memory: stats(2048),
+        }],
+    };
+    assert_eq!(
+        format_report(7, &report, Format::Json),
+        "{\"day\":7,\"part\":1,\"answer\":\"42\",\"parse_ms\":1.000,\"solve_ms\":2.000,\
+         \"parse_peak_bytes\":100,\"allocations\":3,\"allocated_bytes\":4096,\"peak_bytes\":2048}\n"
+    );
+    assert!(format_report(7, &report, Format::Text)
+        .ends_with("Memory for part 1: 3 allocations, 4.0 KiB allocated, 2.0 KiB peak\n"));
+}
+
+#[test]
+fn test_json_string_escapes_control_characters() {
+    assert_eq!(json_string("a\u{1}\\"), "\"a\\u0001\\\\\"");
+}
+
+#[test]
+fn test_parse_format() {
+    assert_eq!("json".parse(), Ok(Format::Json));
+    assert_eq!("text".parse(), Ok(Format::Text));
+    assert!("yaml".parse::<Format>().is_err());
+}
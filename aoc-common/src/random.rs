@@ -0,0 +1,100 @@
+//! A small seeded random number generator, for generating puzzle inputs that
+//! can be reproduced from their seed.
+
+/// SplitMix64: fast, statistically decent, and the same sequence on every
+/// platform for a given seed. Not for anything that needs to be unguessable.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "below(0) has no values to pick from");
+        // Multiplying and keeping the high half avoids most of the bias of
+        // `%`, which is plenty for generating inputs
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// A number from `low` to `high`, inclusive.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "between({}, {}) is empty", low, high);
+        match (high - low).checked_add(1) {
+            Some(n) => low + self.below(n),
+            None => self.next_u64(),
+        }
+    }
+
+    /// An index into a collection of `len` items.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// True `numerator` times out of `denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles the items in place, with every order equally likely.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn test_same_seed_same_numbers() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..5).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+    };
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    // The first output of SplitMix64 seeded with 0
+    assert_eq!(numbers(0)[0], 0xe220_a839_7b1d_cdaf);
+}
+
+#[test]
+fn test_ranges() {
+    let mut rng = Rng::new(1);
+    let mut seen = [false; 6];
+    for _ in 0..1000 {
+        let n = rng.between(3, 8);
+        assert!((3..=8).contains(&n));
+        seen[(n - 3) as usize] = true;
+    }
+    assert!(seen.iter().all(|s| *s));
+    assert_eq!(rng.between(4, 4), 4);
+    assert!(rng.between(0, u64::MAX) > 0 || rng.between(0, u64::MAX) > 0);
+    assert!(!rng.chance(0, 3));
+    assert!(rng.chance(3, 3));
+}
+
+#[test]
+fn test_shuffle() {
+    let mut rng = Rng::new(2);
+    let mut items: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut items);
+    assert_ne!(items, (0..20).collect::<Vec<u32>>());
+    items.sort_unstable();
+    assert_eq!(items, (0..20).collect::<Vec<u32>>());
+}
@@ -0,0 +1,136 @@
+use crate::config::Config;
+use crate::error::Result as SolveResult;
+use crate::log::{self, Level};
+use crate::output::{format_report, Format};
+use crate::solution::{Solution, Solver};
+use std::env;
+use std::process;
+
+pub const DAY_USAGE: &str =
+    "Usage: dayN [<input file> | -] [--format text|json] [--set <name>=<value>]... [-v | -vv | -q]";
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct DayArgs {
+    pub filename: String,
+    pub format: Format,
+    // Parameter overrides, applied on top of config.txt
+    pub overrides: Vec<String>,
+    pub verbosity: Level,
+}
+
+/// The `main` of every day's binary: solves both parts of the input named on
+/// the command line (`input.txt` by default, `-` for stdin) and prints the
+/// answers. Parameters come from `config.txt` and any `--set` overrides.
+/// `-v` and `-vv` turn on debug and trace logging, and `-q` silences progress
+/// messages.
+pub fn run_day<S: Solution>(day: u32, solution: S) {
+    let args = match parse_day_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, DAY_USAGE);
+            process::exit(2);
+        }
+    };
+    log::init(args.verbosity);
+    let solved = configure(solution, &args.overrides)
+        .and_then(|solver| solver.solve(&args.filename, &[1, 2]));
+    match solved {
+        Ok(report) => print!("{}", format_report(day, &report, args.format)),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Configures a solution from `config.txt` and then from `overrides`, each of
+/// which is a `name=value` assignment.
+pub fn configure<S: Solution>(solution: S, overrides: &[String]) -> SolveResult<S> {
+    let mut config = Config::read("config.txt")?;
+    for assignment in overrides {
+        config.set(assignment)?;
+    }
+    solution.with_config(&config)
+}
+
+pub fn parse_day_args<I: Iterator<Item = String>>(args: I) -> Result<DayArgs, String> {
+    parse_day_args_reading(args, "input.txt")
+}
+
+/// Like `parse_day_args`, but reads `default_input` when no input is named.
+pub fn parse_day_args_reading<I: Iterator<Item = String>>(
+    mut args: I,
+    default_input: &str,
+) -> Result<DayArgs, String> {
+    let mut filename = None;
+    let mut format = Format::Text;
+    let mut overrides = Vec::new();
+    let mut verbosity = Level::Info;
+    while let Some(arg) = args.next() {
+        if let Some(level) = log::verbosity_flag(&arg, verbosity) {
+            verbosity = level;
+            continue;
+        }
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format = args.next().ok_or("--format requires a value")?.parse()?
+            }
+            "--set" | "-s" => overrides.push(args.next().ok_or("--set requires name=value")?),
+            _ if filename.is_none() && (arg == "-" || !arg.starts_with('-')) => {
+                filename = Some(arg)
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(DayArgs {
+        filename: filename.unwrap_or_else(|| String::from(default_input)),
+        format,
+        overrides,
+        verbosity,
+    })
+}
+
+#[cfg(test)]
+fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split_whitespace().map(String::from)
+}
+
+#[test]
+fn test_parse_day_args() {
+    assert_eq!(
+        parse_day_args(args("")),
+        Ok(DayArgs {
+            filename: String::from("input.txt"),
+            format: Format::Text,
+            overrides: vec![],
+            verbosity: Level::Info,
+        })
+    );
+    assert_eq!(
+        parse_day_args(args("sample_input.txt --format json --set preamble=5 -vv")),
+        Ok(DayArgs {
+            filename: String::from("sample_input.txt"),
+            format: Format::Json,
+            overrides: vec![String::from("preamble=5")],
+            verbosity: Level::Trace,
+        })
+    );
+    assert_eq!(parse_day_args(args("-")).unwrap().filename, "-");
+    assert_eq!(
+        parse_day_args_reading(args("-q"), "-").unwrap().filename,
+        "-"
+    );
+    assert_eq!(
+        parse_day_args_reading(args("a.txt"), "-").unwrap().filename,
+        "a.txt"
+    );
+    assert_eq!(parse_day_args(args("-q")).unwrap().verbosity, Level::Off);
+    assert!(parse_day_args(args("--format")).is_err());
+    assert!(parse_day_args(args("--set")).is_err());
+    assert!(parse_day_args(args("a.txt b.txt")).is_err());
+    assert!(parse_day_args(args("--loud")).is_err());
+    assert_eq!(
+        parse_day_args(args("--verbose")).unwrap().verbosity,
+        Level::Debug
+    );
+}
@@ -0,0 +1,215 @@
+use crate::alloc::{AllocStats, Phase};
+use crate::config::Config;
+use crate::error::Result;
+use crate::input::{input_name, open};
+use std::fmt;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+/// A day's puzzle, split into parsing the input and solving each part.
+pub trait Solution {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Self::Input>;
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1>;
+
+    /// Also receives the answer to part 1, since some puzzles build on it.
+    fn part2(&self, input: &Self::Input, part1: &Self::Part1) -> Result<Self::Part2>;
+
+    /// Reads the day's parameters. Days without any keep the default, which
+    /// ignores the config.
+    fn configure(&mut self, _config: &Config) -> Result<()> {
+        Ok(())
+    }
+
+    /// Configures the solution, failing if the config has parameters it
+    /// doesn't use.
+    fn with_config(mut self, config: &Config) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        self.configure(config)?;
+        config.check_all_used()?;
+        Ok(self)
+    }
+
+    fn parse_str(&self, input: &str) -> Result<Self::Input> {
+        self.parse(input.as_bytes())
+    }
+
+    /// Parses a file, or stdin when `filename` is `-`.
+    fn parse_file(&self, filename: &str) -> Result<Self::Input> {
+        self.parse(open(filename)?)
+            .map_err(|e| e.in_file(input_name(filename)))
+    }
+}
+
+/// Runs a `Solution` without needing to know its input or answer types, so
+/// every day can be driven the same way.
+pub trait Solver {
+    /// Parses the input and solves the requested parts. Part 1 is always
+    /// solved, because part 2 may need its answer.
+    fn solve_input(&self, input: &mut dyn BufRead, parts: &[u32]) -> Result<Report>;
+
+    /// Solves a file, or stdin when `filename` is `-`. Errors are tagged with
+    /// the name of the input file.
+    fn solve(&self, filename: &str, parts: &[u32]) -> Result<Report> {
+        self.solve_input(&mut open(filename)?, parts)
+            .map_err(|e| e.in_file(input_name(filename)))
+    }
+
+    fn solve_str(&self, input: &str, parts: &[u32]) -> Result<Report> {
+        self.solve_input(&mut input.as_bytes(), parts)
+    }
+}
+
+impl<S: Solution> Solver for S {
+    fn solve_input(&self, input: &mut dyn BufRead, parts: &[u32]) -> Result<Report> {
+        solve(self, input, parts)
+    }
+}
+
+fn solve<S: Solution>(solution: &S, input: &mut dyn BufRead, parts: &[u32]) -> Result<Report> {
+    let phase = Phase::start();
+    let timer = Instant::now();
+    let input = solution.parse(input)?;
+    let parse_time = timer.elapsed();
+    let parse_memory = phase.stats();
+
+    let mut answers = Vec::new();
+    let phase = Phase::start();
+    let timer = Instant::now();
+    let part1 = solution.part1(&input).map_err(|e| e.in_part(1))?;
+    let memory = phase.stats();
+    if parts.contains(&1) {
+        answers.push(PartAnswer {
+            part: 1,
+            answer: part1.to_string(),
+            elapsed: timer.elapsed(),
+            memory,
+        });
+    }
+    if parts.contains(&2) {
+        let phase = Phase::start();
+        let timer = Instant::now();
+        let part2 = solution.part2(&input, &part1).map_err(|e| e.in_part(2))?;
+        let memory = phase.stats();
+        answers.push(PartAnswer {
+            part: 2,
+            answer: part2.to_string(),
+            elapsed: timer.elapsed(),
+            memory,
+        });
+    }
+    Ok(Report {
+        parse_time,
+        parse_memory,
+        answers,
+    })
+}
+
+#[derive(Debug)]
+pub struct PartAnswer {
+    pub part: u32,
+    pub answer: String,
+    pub elapsed: Duration,
+    // Only measured when the CountingAllocator is installed
+    pub memory: Option<AllocStats>,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub parse_time: Duration,
+    pub parse_memory: Option<AllocStats>,
+    pub answers: Vec<PartAnswer>,
+}
+
+impl Report {
+    pub fn answer(&self, part: u32) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|a| a.part == part)
+            .map(|a| a.answer.as_str())
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.answers.iter().map(|a| a.elapsed).sum::<Duration>()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for answer in &self.answers {
+            writeln!(f, "PART {}: {}", answer.part, answer.answer)?;
+        }
+        writeln!(
+            f,
+            "Execution completed in {}ms",
+            self.total_time().as_millis()
+        )?;
+        if let Some(memory) = self.parse_memory {
+            writeln!(f, "Memory for parsing: {}", memory)?;
+        }
+        for answer in &self.answers {
+            if let Some(memory) = answer.memory {
+                writeln!(f, "Memory for part {}: {}", answer.part, memory)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+use crate::error::parse_at;
+
+#[cfg(test)]
+struct Doubler;
+
+#[cfg(test)]
+impl Solution for Doubler {
+    type Input = u32;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<R: BufRead>(&self, mut input: R) -> Result<u32> {
+        let mut line = String::new();
+        input.read_line(&mut line).unwrap();
+        parse_at(1, &line, line.trim_end(), "a number")
+    }
+
+    fn part1(&self, input: &u32) -> Result<u32> {
+        Ok(input * 2)
+    }
+
+    fn part2(&self, _: &u32, part1: &u32) -> Result<u32> {
+        Ok(part1 * 2)
+    }
+}
+
+#[test]
+fn test_solve() {
+    let report = Doubler.solve_str("3", &[1, 2]).unwrap();
+    assert_eq!(report.answer(1), Some("6"));
+    assert_eq!(report.answer(2), Some("12"));
+
+    let report = Doubler.solve_str("3\n", &[2]).unwrap();
+    assert_eq!(report.answer(1), None);
+    assert_eq!(report.answer(2), Some("12"));
+}
+
+#[test]
+fn test_solve_error_names_file() {
+    let error = Doubler.solve("Cargo.toml", &[1]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Cargo.toml:1:1: expected a number, found \"[package]\""
+    );
+    assert_eq!(
+        Doubler.parse_str("x").unwrap_err().to_string(),
+        "<input>:1:1: expected a number, found \"x\""
+    );
+}
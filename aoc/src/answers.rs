@@ -0,0 +1,143 @@
+use crate::days;
+use std::fs;
+use std::path::PathBuf;
+
+/// An answer recorded in a day's `answers.txt`, one per line as
+/// `<input file> <part> <answer> [<name>=<value>]...`, where the trailing
+/// parameters override the day's `config.txt` for that input. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Eq, PartialEq)]
+pub struct KnownAnswer {
+    pub input: String,
+    pub part: u32,
+    pub answer: String,
+    pub params: Vec<String>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub input: String,
+    pub params: Vec<String>,
+    pub part: u32,
+    pub expected: String,
+    pub actual: String,
+}
+
+pub fn manifest_path(day: u32) -> PathBuf {
+    days::day_dir(day).join("answers.txt")
+}
+
+pub fn read_manifest(day: u32) -> Result<Vec<KnownAnswer>, String> {
+    let path = manifest_path(day);
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    parse_manifest(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_manifest(contents: &str) -> Result<Vec<KnownAnswer>, String> {
+    let mut known_answers = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let part = fields.get(1).and_then(|p| p.parse().ok());
+        let params_valid = fields.iter().skip(3).all(|p| p.contains('='));
+        match (fields.len(), part) {
+            (3.., Some(part)) if params_valid => known_answers.push(KnownAnswer {
+                input: String::from(fields[0]),
+                part,
+                answer: String::from(fields[2]),
+                params: fields[3..].iter().map(|p| String::from(*p)).collect(),
+            }),
+            _ => {
+                return Err(format!(
+                    "line {}: expected '<input file> <part> <answer> [<name>=<value>]...'",
+                    i + 1
+                ))
+            }
+        }
+    }
+    Ok(known_answers)
+}
+
+/// Looks up the recorded answer for one input file and part, using the
+/// day's default parameters.
+pub fn expected<'a>(known_answers: &'a [KnownAnswer], input: &str, part: u32) -> Option<&'a str> {
+    known_answers
+        .iter()
+        .find(|k| k.input == input && k.part == part && k.params.is_empty())
+        .map(|k| k.answer.as_str())
+}
+
+/// Runs a day's solver against every input file in its manifest, returning
+/// the answers that don't match.
+pub fn check_day(day: u32) -> Result<Vec<Mismatch>, String> {
+    let known_answers = read_manifest(day)?;
+    // Each input is solved once per distinct set of parameters
+    let mut runs: Vec<(&str, &[String])> = known_answers
+        .iter()
+        .map(|k| (k.input.as_str(), k.params.as_slice()))
+        .collect();
+    runs.sort();
+    runs.dedup();
+
+    let mut mismatches = Vec::new();
+    for (input, params) in runs {
+        let known: Vec<&KnownAnswer> = known_answers
+            .iter()
+            .filter(|k| k.input == input && k.params == params)
+            .collect();
+        let parts: Vec<u32> = known.iter().map(|k| k.part).collect();
+        let solver = days::solver(day, params).map_err(|e| format!("Day {}: {}", day, e))?;
+        let filename = days::day_dir(day).join(input);
+        let report = solver
+            .solve(&filename.to_string_lossy(), &parts)
+            .map_err(|e| format!("Day {}: {}", day, e))?;
+        for known in known {
+            let actual = report.answer(known.part).unwrap_or_default();
+            if known.answer != actual {
+                mismatches.push(Mismatch {
+                    day,
+                    input: String::from(input),
+                    params: params.to_vec(),
+                    part: known.part,
+                    expected: known.answer.clone(),
+                    actual: String::from(actual),
+                });
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
+#[test]
+fn test_parse_manifest() {
+    let known_answers = parse_manifest(
+        "# <input file> <part> <answer>\ninput.txt 1 828\n\ninput.txt 2 565\nsample_input.txt 1 7\n",
+    )
+    .unwrap();
+    assert_eq!(known_answers.len(), 3);
+    assert_eq!(expected(&known_answers, "input.txt", 2), Some("565"));
+    assert_eq!(expected(&known_answers, "sample_input.txt", 1), Some("7"));
+    assert_eq!(expected(&known_answers, "sample_input.txt", 2), None);
+
+    let known_answers = parse_manifest("sample_input.txt 1 127 preamble=5").unwrap();
+    assert_eq!(known_answers[0].params, vec!["preamble=5"]);
+    assert_eq!(expected(&known_answers, "sample_input.txt", 1), None);
+
+    assert!(parse_manifest("input.txt 1").is_err());
+    assert!(parse_manifest("input.txt 1 828 preamble").is_err());
+    assert!(parse_manifest("input.txt one 828").is_err());
+}
+
+#[test]
+fn test_every_day_has_a_manifest() {
+    for day in days::DAYS {
+        let known_answers = read_manifest(day).unwrap();
+        assert!(expected(&known_answers, "input.txt", 1).is_some());
+        assert!(expected(&known_answers, "input.txt", 2).is_some());
+    }
+}
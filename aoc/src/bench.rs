@@ -0,0 +1,418 @@
+use crate::days;
+use aoc_common::alloc;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+// Phases that take well under a millisecond vary by more than any sensible
+// threshold from run to run, so smaller slowdowns than this are ignored.
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+// Likewise for memory, where small phases' peaks depend on buffer growth
+const PEAK_NOISE_FLOOR: u64 = 64 * 1024;
+
+/// Summary of one phase's timings over several runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Uses the nearest-rank method, so every value is one of the samples.
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let rank = |percentile: usize| {
+            let index = (percentile * sorted.len()).div_ceil(100).max(1) - 1;
+            sorted[index]
+        };
+        Stats {
+            min: sorted[0],
+            median: rank(50),
+            p95: rank(95),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>10}  median {:>10}  p95 {:>10}",
+            millis(self.min),
+            millis(self.median),
+            millis(self.p95)
+        )
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// A phase's median time recorded in `benchmarks.txt`, one per line as
+/// `<day> <phase> <median in microseconds> [<peak bytes>]`. The peak is only
+/// recorded by builds with the `count-allocations` feature. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Baseline {
+    pub day: u32,
+    pub phase: String,
+    pub median: Duration,
+    pub peak_bytes: Option<u64>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: String,
+    pub baseline: Duration,
+    pub median: Duration,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = self.median.as_secs_f64() / self.baseline.as_secs_f64() - 1.0;
+        write!(
+            f,
+            "Day {} {} regressed: median {} vs baseline {} (+{:.0}%)",
+            self.day,
+            self.phase,
+            millis(self.median),
+            millis(self.baseline),
+            change * 100.0
+        )
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct MemoryRegression {
+    pub day: u32,
+    pub phase: String,
+    pub baseline: u64,
+    pub peak_bytes: u64,
+}
+
+impl fmt::Display for MemoryRegression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let change = self.peak_bytes as f64 / self.baseline.max(1) as f64 - 1.0;
+        write!(
+            f,
+            "Day {} {} regressed: peak {} vs baseline {} (+{:.0}%)",
+            self.day,
+            self.phase,
+            alloc::format_bytes(self.peak_bytes),
+            alloc::format_bytes(self.baseline),
+            change * 100.0
+        )
+    }
+}
+
+pub fn baselines_path() -> PathBuf {
+    days::workspace_root().join("benchmarks.txt")
+}
+
+/// Reads the stored baselines, treating a missing file as having none.
+pub fn read_baselines() -> Result<Vec<Baseline>, String> {
+    let path = baselines_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    parse_baselines(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_baselines(contents: &str) -> Result<Vec<Baseline>, String> {
+    let mut baselines = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let day = fields.first().and_then(|d| d.parse().ok());
+        let micros = fields.get(2).and_then(|m| m.parse().ok());
+        let peak_bytes = fields.get(3).map(|p| p.parse().ok());
+        match (fields.len(), day, micros, peak_bytes) {
+            (3 | 4, Some(day), Some(micros), None | Some(Some(_)))
+                if PHASES.contains(&fields[1]) =>
+            {
+                baselines.push(Baseline {
+                    day,
+                    phase: String::from(fields[1]),
+                    median: Duration::from_micros(micros),
+                    peak_bytes: peak_bytes.flatten(),
+                })
+            }
+            _ => {
+                return Err(format!(
+                    "line {}: expected '<day> <parse|part1|part2> <median in microseconds> [<peak bytes>]'",
+                    i + 1
+                ))
+            }
+        }
+    }
+    Ok(baselines)
+}
+
+pub fn format_baselines(baselines: &[Baseline]) -> String {
+    let mut contents = String::from("# <day> <phase> <median in microseconds> [<peak bytes>]\n");
+    for baseline in baselines {
+        contents.push_str(&format!(
+            "{} {} {}",
+            baseline.day,
+            baseline.phase,
+            baseline.median.as_micros()
+        ));
+        if let Some(peak_bytes) = baseline.peak_bytes {
+            contents.push_str(&format!(" {}", peak_bytes));
+        }
+        contents.push('\n');
+    }
+    contents
+}
+
+/// Replaces the baselines for the days that were just benchmarked, keeping
+/// the rest, and writes them back to `benchmarks.txt`.
+pub fn save_baselines(results: &[DayBench]) -> Result<(), String> {
+    let mut baselines: Vec<Baseline> = read_baselines()?
+        .into_iter()
+        .filter(|b| !results.iter().any(|r| r.day == b.day))
+        .collect();
+    for result in results {
+        for (i, phase) in PHASES.iter().enumerate() {
+            baselines.push(Baseline {
+                day: result.day,
+                phase: String::from(*phase),
+                median: result.phases[i].median,
+                peak_bytes: result.peaks[i],
+            });
+        }
+    }
+    baselines.sort_by_key(|b| (b.day, PHASES.iter().position(|p| *p == b.phase)));
+    let path = baselines_path();
+    fs::write(&path, format_baselines(&baselines))
+        .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))
+}
+
+/// Orders days by their total baseline time, slowest first, so that running
+/// them in parallel doesn't leave a slow day until last. Days without
+/// baselines keep their order at the end.
+pub fn slowest_first(days: &[u32], baselines: &[Baseline]) -> Vec<u32> {
+    let total = |day: u32| -> Duration {
+        baselines
+            .iter()
+            .filter(|b| b.day == day)
+            .map(|b| b.median)
+            .sum()
+    };
+    let mut order = days.to_vec();
+    order.sort_by_key(|&day| std::cmp::Reverse(total(day)));
+    order
+}
+
+/// Timings for one day, with the phases in the same order as `PHASES`, and
+/// each phase's largest peak memory when allocations are being counted.
+#[derive(Debug)]
+pub struct DayBench {
+    pub day: u32,
+    pub phases: Vec<Stats>,
+    pub peaks: Vec<Option<u64>>,
+}
+
+/// Solves a day's input `runs` times and summarizes each phase.
+pub fn bench_day(day: u32, runs: usize) -> Result<DayBench, String> {
+    let solver = days::solver(day, &[])?;
+    let input = days::default_input(day);
+    let mut samples: Vec<Vec<Duration>> = vec![Vec::new(); PHASES.len()];
+    let mut peaks: Vec<Option<u64>> = vec![None; PHASES.len()];
+    let mut record_peak = |phase: usize, memory: Option<alloc::AllocStats>| {
+        if let Some(memory) = memory {
+            peaks[phase] = Some(peaks[phase].unwrap_or(0).max(memory.peak_bytes));
+        }
+    };
+    for _ in 0..runs {
+        let report = solver
+            .solve(&input.to_string_lossy(), &[1, 2])
+            .map_err(|e| format!("Day {}: {}", day, e))?;
+        samples[0].push(report.parse_time);
+        record_peak(0, report.parse_memory);
+        for answer in &report.answers {
+            samples[answer.part as usize].push(answer.elapsed);
+            record_peak(answer.part as usize, answer.memory);
+        }
+    }
+    Ok(DayBench {
+        day,
+        phases: samples.iter().map(|s| Stats::from_samples(s)).collect(),
+        peaks,
+    })
+}
+
+/// Compares medians against the baselines. Phases without a baseline, or
+/// that slowed down by no more than `threshold` percent or by less than a
+/// millisecond, aren't reported.
+pub fn find_regressions(
+    result: &DayBench,
+    baselines: &[Baseline],
+    threshold: u32,
+) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    for (phase, stats) in PHASES.iter().zip(&result.phases) {
+        let baseline = baselines
+            .iter()
+            .find(|b| b.day == result.day && b.phase == *phase);
+        if let Some(baseline) = baseline {
+            let limit = baseline.median.as_secs_f64() * (1.0 + f64::from(threshold) / 100.0);
+            if stats.median.as_secs_f64() > limit && stats.median > baseline.median + NOISE_FLOOR {
+                regressions.push(Regression {
+                    day: result.day,
+                    phase: String::from(*phase),
+                    baseline: baseline.median,
+                    median: stats.median,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+/// Compares peak memory against the baselines that recorded one. Phases
+/// whose peak grew by no more than `threshold` percent or by less than 64 KiB
+/// aren't reported.
+pub fn find_memory_regressions(
+    result: &DayBench,
+    baselines: &[Baseline],
+    threshold: u32,
+) -> Vec<MemoryRegression> {
+    let mut regressions = Vec::new();
+    for (phase, peak_bytes) in PHASES.iter().zip(&result.peaks) {
+        let baseline = baselines
+            .iter()
+            .find(|b| b.day == result.day && b.phase == *phase)
+            .and_then(|b| b.peak_bytes);
+        if let (Some(baseline), Some(peak_bytes)) = (baseline, *peak_bytes) {
+            let limit = baseline as f64 * (1.0 + f64::from(threshold) / 100.0);
+            if peak_bytes as f64 > limit && peak_bytes > baseline + PEAK_NOISE_FLOOR {
+                regressions.push(MemoryRegression {
+                    day: result.day,
+                    phase: String::from(*phase),
+                    baseline,
+                    peak_bytes,
+                });
+            }
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn test_stats() {
+    let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+    let stats = Stats::from_samples(&samples);
+    assert_eq!(stats.min, ms(1));
+    assert_eq!(stats.median, ms(10));
+    assert_eq!(stats.p95, ms(19));
+
+    let stats = Stats::from_samples(&[ms(7)]);
+    assert_eq!((stats.min, stats.median, stats.p95), (ms(7), ms(7), ms(7)));
+}
+
+#[test]
+fn test_parse_baselines() {
+    let baselines = parse_baselines("# comment\n3 parse 120\n\n3 part2 4500 2048\n").unwrap();
+    assert_eq!(
+        baselines,
+        vec![
+            Baseline {
+                day: 3,
+                phase: String::from("parse"),
+                median: Duration::from_micros(120),
+                peak_bytes: None,
+            },
+            Baseline {
+                day: 3,
+                phase: String::from("part2"),
+                median: Duration::from_micros(4500),
+                peak_bytes: Some(2048),
+            },
+        ]
+    );
+    assert_eq!(
+        parse_baselines(&format_baselines(&baselines)),
+        Ok(baselines)
+    );
+
+    assert!(parse_baselines("3 part3 100").is_err());
+    assert!(parse_baselines("3 parse 1.5").is_err());
+    assert!(parse_baselines("3 parse 15 1.5").is_err());
+    assert!(parse_baselines("3 parse 15 100 7").is_err());
+}
+
+#[test]
+fn test_find_regressions() {
+    let baselines = parse_baselines("1 parse 1000\n1 part1 1000\n").unwrap();
+    let result = DayBench {
+        day: 1,
+        phases: vec![
+            Stats::from_samples(&[ms(5)]),
+            Stats::from_samples(&[ms(1)]),
+            Stats::from_samples(&[ms(9)]),
+        ],
+        peaks: vec![None; 3],
+    };
+    let regressions = find_regressions(&result, &baselines, 20);
+    assert_eq!(regressions.len(), 1);
+    assert_eq!(regressions[0].phase, "parse");
+    assert!(find_regressions(&result, &baselines, 500).is_empty());
+
+    let baselines = parse_baselines("1 parse 100\n").unwrap();
+    let result = DayBench {
+        day: 1,
+        phases: vec![Stats::from_samples(&[Duration::from_micros(900)])],
+        peaks: vec![None],
+    };
+    assert!(find_regressions(&result, &baselines, 20).is_empty());
+}
+
+#[test]
+fn test_find_memory_regressions() {
+    let baselines = parse_baselines("1 parse 10 100000\n1 part1 10 1000\n1 part2 10\n").unwrap();
+    let result = DayBench {
+        day: 1,
+        phases: vec![Stats::from_samples(&[ms(0)]); 3],
+        peaks: vec![Some(200000), Some(50000), Some(900000)],
+    };
+    // part1 grew fiftyfold but by less than 64 KiB, and part2 has no baseline
+    let regressions = find_memory_regressions(&result, &baselines, 20);
+    assert_eq!(regressions.len(), 1);
+    assert_eq!(
+        regressions[0].to_string(),
+        "Day 1 parse regressed: peak 195.3 KiB vs baseline 97.7 KiB (+100%)"
+    );
+    assert!(find_memory_regressions(&result, &baselines, 150).is_empty());
+}
+
+#[test]
+fn test_slowest_first() {
+    let baselines = parse_baselines("1 parse 10\n2 part1 500\n2 part2 1\n3 part2 100\n").unwrap();
+    assert_eq!(slowest_first(&[1, 2, 3, 4], &baselines), vec![2, 3, 1, 4]);
+}
+
+#[test]
+fn test_every_baseline_is_valid() {
+    for baseline in read_baselines().unwrap() {
+        assert!(days::DAYS.contains(&baseline.day));
+    }
+}
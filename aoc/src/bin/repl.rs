@@ -0,0 +1,60 @@
+//! Explores the puzzles interactively, calling into the days' functions
+//! without rebuilding. Type `help` for the commands.
+
+use aoc::days;
+use aoc::repl::Repl;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+
+// Only the most recent commands are kept from one session to the next
+const HISTORY_LIMIT: usize = 1000;
+
+fn main() {
+    let history_path = days::workspace_root().join(".repl_history");
+    let history: Vec<String> = fs::read_to_string(&history_path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+    let skip = history.len().saturating_sub(HISTORY_LIMIT);
+    let mut repl = Repl::with_history(history[skip..].to_vec());
+    // Rewritten on exit, so the file stays within the limit
+    let mut saved = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&history_path)
+        .ok();
+
+    println!("Type help for the commands, quit to leave.");
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("aoc> ");
+        io::stdout().flush().unwrap();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        if matches!(line.trim(), "quit" | "exit") {
+            break;
+        }
+        let recorded = repl.history().len();
+        match repl.eval(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output),
+            Err(message) => eprintln!("error: {}", message),
+        }
+        // Keep each new command even if the session is killed
+        if let (Some(file), Some(command)) = (saved.as_mut(), repl.history().get(recorded)) {
+            let _ = writeln!(file, "{}", command);
+        }
+    }
+    let history = repl.history();
+    let kept = &history[history.len().saturating_sub(HISTORY_LIMIT)..];
+    let contents: String = kept
+        .iter()
+        .map(|command| format!("{}\n", command))
+        .collect();
+    let _ = fs::write(&history_path, contents);
+}
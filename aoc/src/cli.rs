@@ -0,0 +1,519 @@
+use crate::days::{CALENDAR, DAYS, PARTS};
+use crate::server::Limits;
+use aoc_common::log::{self, Level};
+use aoc_common::Format;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage:
+    aoc run --day <N> [--part <P>] [--input <PATH> | -] [--format text|json]
+                 [--set <name>=<value>]... [--timeout <DURATION>]
+    aoc run --all [--jobs <N>] [--format text|json] [--timeout <DURATION>]
+    aoc bench (--day <N> | --all) [--runs <N>] [--threshold <PERCENT>] [--save]
+    aoc new --day <N>
+    aoc fingerprint (--day <N> | --all) [--save]
+    aoc serve [--port <N>] [--max-body <BYTES>] [--timeout <DURATION>]
+              [--max-connections <N>]
+    aoc watch --day <N> [--interval <DURATION>]
+    aoc generate --day <N> [--seed <N>]
+
+Add -v or -vv to either command for debug or trace logging, or -q to hide
+progress messages. AOC_LOG=day11=trace,day7 sets the level per day.";
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    // Creates the crate for a new day
+    New(u32),
+    Watch(WatchOptions),
+    // Checks the days' inputs against their recorded fingerprints, or
+    // records new ones
+    Fingerprint { days: Vec<u32>, save: bool },
+    Serve(ServeOptions),
+    // Writes out a generated input, from a random seed unless one is given
+    Generate { day: u32, seed: Option<u64> },
+    Help,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct RunOptions {
+    pub days: Vec<u32>,
+    pub parts: Vec<u32>,
+    // Only allowed when running a single day; otherwise each day's input.txt is
+    // used. "-" reads from stdin.
+    pub input: Option<PathBuf>,
+    pub format: Format,
+    // Parameter overrides, applied on top of the day's config.txt. Only
+    // allowed when running a single day.
+    pub overrides: Vec<String>,
+    // Worker threads for running several days at once, defaulting to one per
+    // CPU. Only allowed with --all.
+    pub jobs: Option<usize>,
+    // How long each day may take, like "5s" or "500ms". Days are stopped the
+    // next time they check for cancellation after it runs out.
+    pub timeout: Option<Duration>,
+    pub verbosity: Level,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct BenchOptions {
+    pub days: Vec<u32>,
+    pub runs: usize,
+    // How many percent slower than its baseline a phase's median can get
+    pub threshold: u32,
+    // Overwrite the baselines with this run's medians instead of comparing
+    pub save: bool,
+    pub verbosity: Level,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct WatchOptions {
+    // Any day with a directory, including ones the runner can't solve yet
+    pub day: u32,
+    // How often to check the day's files for changes
+    pub interval: Duration,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct ServeOptions {
+    // 0 lets the system pick a free port
+    pub port: u16,
+    pub limits: Limits,
+    pub verbosity: Level,
+}
+
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("new") => parse_new(args).map(Command::New),
+        Some("watch") => parse_watch(args).map(Command::Watch),
+        Some("fingerprint") => parse_fingerprint(args),
+        Some("serve") => parse_serve(args).map(Command::Serve),
+        Some("generate") => parse_generate(args),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("Unknown command '{}'", other)),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<RunOptions, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut all = false;
+    let mut format = Format::Text;
+    let mut overrides = Vec::new();
+    let mut jobs = None;
+    let mut timeout = None;
+    let mut verbosity = Level::Info;
+    while let Some(arg) = args.next() {
+        if let Some(level) = log::verbosity_flag(&arg, verbosity) {
+            verbosity = level;
+            continue;
+        }
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &DAYS)?),
+            "--part" | "-p" => part = Some(parse_number(&arg, args.next(), &PARTS)?),
+            "--input" | "-i" => {
+                input = Some(PathBuf::from(args.next().ok_or("--input requires a path")?))
+            }
+            "--format" | "-f" => {
+                format = args.next().ok_or("--format requires a value")?.parse()?
+            }
+            "--jobs" | "-j" => jobs = Some(parse_number(&arg, args.next(), &(1..=256))? as usize),
+            "--timeout" | "-t" => timeout = Some(parse_duration(&arg, args.next())?),
+            "--set" | "-s" => overrides.push(args.next().ok_or("--set requires name=value")?),
+            "--all" => all = true,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    let days = select_days(day, all)?;
+    if all && input.is_some() {
+        return Err(String::from("--input can only be used with --day"));
+    }
+    if all && !overrides.is_empty() {
+        return Err(String::from("--set can only be used with --day"));
+    }
+    if !all && jobs.is_some() {
+        return Err(String::from("--jobs can only be used with --all"));
+    }
+    let parts = match part {
+        Some(part) => vec![part],
+        None => PARTS.collect(),
+    };
+    Ok(RunOptions {
+        days,
+        parts,
+        input,
+        format,
+        overrides,
+        jobs,
+        timeout,
+        verbosity,
+    })
+}
+
+fn parse_bench<I: Iterator<Item = String>>(mut args: I) -> Result<BenchOptions, String> {
+    let mut day = None;
+    let mut all = false;
+    let mut runs = 10;
+    let mut threshold = 20;
+    let mut save = false;
+    let mut verbosity = Level::Info;
+    while let Some(arg) = args.next() {
+        if let Some(level) = log::verbosity_flag(&arg, verbosity) {
+            verbosity = level;
+            continue;
+        }
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &DAYS)?),
+            "--all" => all = true,
+            "--runs" | "-n" => runs = parse_number(&arg, args.next(), &(1..=10_000))? as usize,
+            "--threshold" => threshold = parse_number(&arg, args.next(), &(0..=10_000))?,
+            "--save" => save = true,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(BenchOptions {
+        days: select_days(day, all)?,
+        runs,
+        threshold,
+        save,
+        verbosity,
+    })
+}
+
+fn parse_new<I: Iterator<Item = String>>(mut args: I) -> Result<u32, String> {
+    let mut day = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &CALENDAR)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    day.ok_or_else(|| String::from("--day is required"))
+}
+
+fn parse_fingerprint<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut all = false;
+    let mut save = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &DAYS)?),
+            "--all" => all = true,
+            "--save" => save = true,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Fingerprint {
+        days: select_days(day, all)?,
+        save,
+    })
+}
+
+fn parse_generate<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut day = None;
+    let mut seed = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &DAYS)?),
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a value")?;
+                let parsed = value
+                    .parse()
+                    .map_err(|_| format!("--seed must be a number, got '{}'", value))?;
+                seed = Some(parsed);
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Generate {
+        day: day.ok_or("--day is required")?,
+        seed,
+    })
+}
+
+fn parse_serve<I: Iterator<Item = String>>(mut args: I) -> Result<ServeOptions, String> {
+    let mut port = 8020;
+    let mut limits = Limits::default();
+    let mut verbosity = Level::Info;
+    while let Some(arg) = args.next() {
+        if let Some(level) = log::verbosity_flag(&arg, verbosity) {
+            verbosity = level;
+            continue;
+        }
+        match arg.as_str() {
+            "--port" => port = parse_number(&arg, args.next(), &(0..=65535))? as u16,
+            "--max-body" => {
+                limits.max_body = parse_number(&arg, args.next(), &(1..=u32::MAX))? as usize
+            }
+            "--timeout" | "-t" => limits.timeout = parse_duration(&arg, args.next())?,
+            "--max-connections" => {
+                limits.max_connections = parse_number(&arg, args.next(), &(1..=1024))? as usize
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(ServeOptions {
+        port,
+        limits,
+        verbosity,
+    })
+}
+
+fn parse_watch<I: Iterator<Item = String>>(mut args: I) -> Result<WatchOptions, String> {
+    let mut day = None;
+    let mut interval = Duration::from_millis(500);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_number(&arg, args.next(), &CALENDAR)?),
+            "--interval" => interval = parse_duration(&arg, args.next())?,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    if interval.is_zero() {
+        return Err(String::from("--interval must be more than 0"));
+    }
+    Ok(WatchOptions {
+        day: day.ok_or("--day is required")?,
+        interval,
+    })
+}
+
+fn select_days(day: Option<u32>, all: bool) -> Result<Vec<u32>, String> {
+    match (day, all) {
+        (Some(_), true) => Err(String::from("--day and --all can't be used together")),
+        (Some(day), false) => Ok(vec![day]),
+        (None, true) => Ok(DAYS.collect()),
+        (None, false) => Err(String::from("Either --day or --all is required")),
+    }
+}
+
+/// Parses a duration like "5s", "500ms" or "2m". A bare number is seconds.
+fn parse_duration(flag: &str, value: Option<String>) -> Result<Duration, String> {
+    let value = value.ok_or_else(|| format!("{} requires a duration", flag))?;
+    let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let amount = digits.parse::<u64>().ok();
+    let duration = match (amount, &value[digits.len()..]) {
+        (Some(ms), "ms") => Some(Duration::from_millis(ms)),
+        (Some(s), "s") | (Some(s), "") => Some(Duration::from_secs(s)),
+        (Some(m), "m") => m.checked_mul(60).map(Duration::from_secs),
+        _ => None,
+    };
+    duration.ok_or_else(|| {
+        format!(
+            "{} must be a duration like 5s or 500ms, got '{}'",
+            flag, value
+        )
+    })
+}
+
+fn parse_number(
+    flag: &str,
+    value: Option<String>,
+    allowed: &std::ops::RangeInclusive<u32>,
+) -> Result<u32, String> {
+    let value = value.ok_or_else(|| format!("{} requires a value", flag))?;
+    match value.parse::<u32>() {
+        Ok(n) if allowed.contains(&n) => Ok(n),
+        _ => Err(format!(
+            "{} must be between {} and {}, got '{}'",
+            flag,
+            allowed.start(),
+            allowed.end(),
+            value
+        )),
+    }
+}
+
+#[cfg(test)]
+fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split_whitespace().map(String::from)
+}
+
+#[test]
+fn test_parse_run() {
+    assert_eq!(
+        parse_args(args("run --day 11 --part 2 --input foo.txt")),
+        Ok(Command::Run(RunOptions {
+            days: vec![11],
+            parts: vec![2],
+            input: Some(PathBuf::from("foo.txt")),
+            format: Format::Text,
+            overrides: vec![],
+            jobs: None,
+            timeout: None,
+            verbosity: Level::Info,
+        }))
+    );
+    assert_eq!(
+        parse_args(args(
+            "run --day 3 --format json --set slope=1,2 -s width=11 -v"
+        )),
+        Ok(Command::Run(RunOptions {
+            days: vec![3],
+            parts: vec![1, 2],
+            input: None,
+            format: Format::Json,
+            overrides: vec![String::from("slope=1,2"), String::from("width=11")],
+            jobs: None,
+            timeout: None,
+            verbosity: Level::Debug,
+        }))
+    );
+    match parse_args(args("run --all")) {
+        Ok(Command::Run(options)) => assert_eq!(options.days.len(), 16),
+        other => panic!("Unexpected result {:?}", other),
+    }
+    match parse_args(args("run --all -j 4 --timeout 5s")) {
+        Ok(Command::Run(options)) => {
+            assert_eq!(options.jobs, Some(4));
+            assert_eq!(options.timeout, Some(Duration::from_secs(5)));
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_bench() {
+    assert_eq!(
+        parse_args(args("bench --day 15 --runs 3 --threshold 50 --save -q")),
+        Ok(Command::Bench(BenchOptions {
+            days: vec![15],
+            runs: 3,
+            threshold: 50,
+            save: true,
+            verbosity: Level::Off,
+        }))
+    );
+    match parse_args(args("bench --all")) {
+        Ok(Command::Bench(options)) => {
+            assert_eq!(options.days.len(), 16);
+            assert_eq!(options.runs, 10);
+            assert!(!options.save);
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+    assert!(parse_args(args("bench")).is_err());
+    assert!(parse_args(args("bench --day 1 --runs 0")).is_err());
+}
+
+#[test]
+fn test_parse_new() {
+    assert_eq!(parse_args(args("new --day 17")), Ok(Command::New(17)));
+    assert!(parse_args(args("new")).is_err());
+    assert!(parse_args(args("new --day 26")).is_err());
+    assert!(parse_args(args("new --day 17 --all")).is_err());
+}
+
+#[test]
+fn test_parse_fingerprint() {
+    assert_eq!(
+        parse_args(args("fingerprint --day 14 --save")),
+        Ok(Command::Fingerprint {
+            days: vec![14],
+            save: true,
+        })
+    );
+    match parse_args(args("fingerprint --all")) {
+        Ok(Command::Fingerprint { days, save }) => assert_eq!((days.len(), save), (16, false)),
+        other => panic!("Unexpected result {:?}", other),
+    }
+    assert!(parse_args(args("fingerprint")).is_err());
+}
+
+#[test]
+fn test_parse_generate() {
+    assert_eq!(
+        parse_args(args("generate --day 9 --seed 42")),
+        Ok(Command::Generate {
+            day: 9,
+            seed: Some(42)
+        })
+    );
+    assert_eq!(
+        parse_args(args("generate -d 16")),
+        Ok(Command::Generate {
+            day: 16,
+            seed: None
+        })
+    );
+    assert!(parse_args(args("generate --seed 1")).is_err());
+    assert!(parse_args(args("generate --day 9 --seed -1")).is_err());
+}
+
+#[test]
+fn test_parse_serve() {
+    assert_eq!(
+        parse_args(args("serve")),
+        Ok(Command::Serve(ServeOptions {
+            port: 8020,
+            limits: Limits::default(),
+            verbosity: Level::Info,
+        }))
+    );
+    match parse_args(args(
+        "serve --port 0 --max-body 100 --timeout 2s --max-connections 4 -q",
+    )) {
+        Ok(Command::Serve(options)) => {
+            assert_eq!(options.port, 0);
+            assert_eq!(options.limits.max_body, 100);
+            assert_eq!(options.limits.timeout, Duration::from_secs(2));
+            assert_eq!(options.limits.max_connections, 4);
+            assert_eq!(options.verbosity, Level::Off);
+        }
+        other => panic!("Unexpected result {:?}", other),
+    }
+    assert!(parse_args(args("serve --port 70000")).is_err());
+    assert!(parse_args(args("serve --max-body 0")).is_err());
+    assert!(parse_args(args("serve --max-connections 0")).is_err());
+}
+
+#[test]
+fn test_parse_watch() {
+    assert_eq!(
+        parse_args(args("watch --day 17")),
+        Ok(Command::Watch(WatchOptions {
+            day: 17,
+            interval: Duration::from_millis(500),
+        }))
+    );
+    match parse_args(args("watch -d 3 --interval 2s")) {
+        Ok(Command::Watch(options)) => assert_eq!(options.interval, Duration::from_secs(2)),
+        other => panic!("Unexpected result {:?}", other),
+    }
+    assert!(parse_args(args("watch")).is_err());
+    assert!(parse_args(args("watch --day 3 --interval 0ms")).is_err());
+    assert!(parse_args(args("watch --all")).is_err());
+}
+
+#[test]
+fn test_parse_duration() {
+    let duration = |s: &str| parse_duration("--timeout", Some(String::from(s)));
+    assert_eq!(duration("500ms"), Ok(Duration::from_millis(500)));
+    assert_eq!(duration("5s"), Ok(Duration::from_secs(5)));
+    assert_eq!(duration("7"), Ok(Duration::from_secs(7)));
+    assert_eq!(duration("2m"), Ok(Duration::from_secs(120)));
+    assert!(duration("5h").is_err());
+    assert!(duration("s").is_err());
+    assert!(duration("-1s").is_err());
+    assert!(duration("999999999999999999m").is_err());
+    assert!(parse_duration("--timeout", None).is_err());
+}
+
+#[test]
+fn test_parse_run_errors() {
+    assert!(parse_args(args("run")).is_err());
+    assert!(parse_args(args("run --day 17")).is_err());
+    assert!(parse_args(args("run --day 1 --part 3")).is_err());
+    assert!(parse_args(args("run --all --day 1")).is_err());
+    assert!(parse_args(args("run --all --input foo.txt")).is_err());
+    assert!(parse_args(args("run --day 1 --format xml")).is_err());
+    assert!(parse_args(args("run --all --set target=10")).is_err());
+    assert!(parse_args(args("run --day 1 --jobs 2")).is_err());
+    assert!(parse_args(args("run --all --jobs 0")).is_err());
+    assert!(parse_args(args("run --day 1 --set")).is_err());
+    assert!(parse_args(args("fly")).is_err());
+}
@@ -0,0 +1,78 @@
+use aoc_common::{Config, Solution, Solver};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+pub const DAYS: RangeInclusive<u32> = 1..=16;
+pub const PARTS: RangeInclusive<u32> = 1..=2;
+// Every day of the calendar, including ones without a solver yet
+pub const CALENDAR: RangeInclusive<u32> = 1..=25;
+
+/// Builds a day's solver, configured from its `config.txt` and then from
+/// `overrides`, each of which is a `name=value` assignment.
+pub fn solver(day: u32, overrides: &[String]) -> Result<Box<dyn Solver>, String> {
+    let mut config =
+        Config::read(&config_path(day).to_string_lossy()).map_err(|e| e.to_string())?;
+    for assignment in overrides {
+        config.set(assignment).map_err(|e| e.to_string())?;
+    }
+    match day {
+        1 => configured(day1::Day1::<i64>::default(), &config),
+        2 => configured(day2::Day2, &config),
+        3 => configured(day3::Day3::default(), &config),
+        4 => configured(day4::Day4, &config),
+        5 => configured(day5::Day5, &config),
+        6 => configured(day6::Day6, &config),
+        7 => configured(day7::Day7::default(), &config),
+        8 => configured(day8::Day8, &config),
+        9 => configured(day9::Day9::default(), &config),
+        10 => configured(day10::Day10, &config),
+        11 => configured(day11::Day11, &config),
+        12 => configured(day12::Day12, &config),
+        13 => configured(day13::Day13, &config),
+        14 => configured(day14::Day14, &config),
+        15 => configured(day15::Day15::default(), &config),
+        16 => configured(day16::Day16::default(), &config),
+        _ => Err(format!("No solver for day {}", day)),
+    }
+}
+
+fn configured<S: Solution + 'static>(
+    solution: S,
+    config: &Config,
+) -> Result<Box<dyn Solver>, String> {
+    match solution.with_config(config) {
+        Ok(solution) => Ok(Box::new(solution)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The directory holding the `dayN` crates.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+pub fn day_dir(day: u32) -> PathBuf {
+    workspace_root().join(format!("day{}", day))
+}
+
+pub fn config_path(day: u32) -> PathBuf {
+    day_dir(day).join("config.txt")
+}
+
+pub fn default_input(day: u32) -> PathBuf {
+    day_dir(day).join("input.txt")
+}
+
+#[test]
+fn test_default_input() {
+    assert!(default_input(5).ends_with("day5/input.txt"));
+    assert!(default_input(5).exists());
+}
+
+#[test]
+fn test_solver_overrides() {
+    assert!(solver(9, &[String::from("preamble=5")]).is_ok());
+    assert!(solver(9, &[String::from("preamble=five")]).is_err());
+    assert!(solver(2, &[String::from("preamble=5")]).is_err());
+    assert!(solver(17, &[]).is_err());
+}
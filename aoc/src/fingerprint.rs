@@ -0,0 +1,318 @@
+use crate::days;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// A summary of an input file, recorded in a day's `fingerprints.txt` so that
+/// a different or damaged input is noticed before its answers are compared
+/// with the recorded ones. Each line is
+/// `<input file> <hash> lines=<N> blank=<N> width=<min>-<max> chars=<classes>`.
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fingerprint {
+    pub input: String,
+    // FNV-1a of the file's bytes, which unlike std's hashers is stable
+    pub hash: u64,
+    pub lines: usize,
+    pub blank_lines: usize,
+    pub min_width: usize,
+    pub max_width: usize,
+    // The kinds of characters used, like "0-9a-z:-"
+    pub chars: String,
+}
+
+impl Fingerprint {
+    pub fn of(input: &str, contents: &[u8]) -> Fingerprint {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in contents {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        let text = String::from_utf8_lossy(contents);
+        let lines: Vec<&str> = text.lines().collect();
+        let widths = lines
+            .iter()
+            .filter(|l| !l.is_empty())
+            .map(|l| l.chars().count());
+        Fingerprint {
+            input: String::from(input),
+            hash,
+            lines: lines.len(),
+            blank_lines: lines.iter().filter(|l| l.is_empty()).count(),
+            min_width: widths.clone().min().unwrap_or(0),
+            max_width: widths.max().unwrap_or(0),
+            chars: char_classes(&text),
+        }
+    }
+
+    /// How this fingerprint differs from the recorded one, in words. The hash
+    /// is only mentioned when nothing else gives the difference away.
+    pub fn differences(&self, recorded: &Fingerprint) -> Vec<String> {
+        let mut differences = Vec::new();
+        if self.lines != recorded.lines {
+            differences.push(format!(
+                "{} lines instead of {}",
+                self.lines, recorded.lines
+            ));
+        }
+        if self.blank_lines != recorded.blank_lines {
+            differences.push(format!(
+                "{} blank lines instead of {}",
+                self.blank_lines, recorded.blank_lines
+            ));
+        }
+        if (self.min_width, self.max_width) != (recorded.min_width, recorded.max_width) {
+            differences.push(format!(
+                "lines {}-{} characters wide instead of {}-{}",
+                self.min_width, self.max_width, recorded.min_width, recorded.max_width
+            ));
+        }
+        if self.chars != recorded.chars {
+            differences.push(format!("uses {} instead of {}", self.chars, recorded.chars));
+        }
+        if differences.is_empty() && self.hash != recorded.hash {
+            differences.push(String::from("different contents"));
+        }
+        differences
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:016x} lines={} blank={} width={}-{} chars={}",
+            self.input,
+            self.hash,
+            self.lines,
+            self.blank_lines,
+            self.min_width,
+            self.max_width,
+            self.chars
+        )
+    }
+}
+
+// Digits and letters are collapsed into ranges, and the other characters are
+// listed in order. Spaces and backslashes are escaped so the result can't
+// be split up by whitespace.
+fn char_classes(text: &str) -> String {
+    let used: BTreeSet<char> = text.chars().filter(|c| *c != '\n' && *c != '\r').collect();
+    let mut classes = String::new();
+    for (range, test) in [
+        ("0-9", char::is_ascii_digit as fn(&char) -> bool),
+        ("a-z", char::is_ascii_lowercase),
+        ("A-Z", char::is_ascii_uppercase),
+    ] {
+        if used.iter().any(test) {
+            classes.push_str(range);
+        }
+    }
+    for c in used.iter().filter(|c| !c.is_ascii_alphanumeric()) {
+        match c {
+            ' ' => classes.push_str("\\s"),
+            '\\' => classes.push_str("\\\\"),
+            _ => classes.push(*c),
+        }
+    }
+    if classes.is_empty() {
+        classes.push('-');
+    }
+    classes
+}
+
+pub fn manifest_path(day: u32) -> PathBuf {
+    days::day_dir(day).join("fingerprints.txt")
+}
+
+/// Reads a day's fingerprints, treating a missing file as having none.
+pub fn read_manifest(day: u32) -> Result<Vec<Fingerprint>, String> {
+    let path = manifest_path(day);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    parse_manifest(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_manifest(contents: &str) -> Result<Vec<Fingerprint>, String> {
+    let mut fingerprints = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fingerprint = parse_line(line).ok_or_else(|| {
+            format!(
+                "line {}: expected '<input file> <hash> lines=<N> blank=<N> width=<min>-<max> chars=<classes>'",
+                i + 1
+            )
+        })?;
+        fingerprints.push(fingerprint);
+    }
+    Ok(fingerprints)
+}
+
+fn parse_line(line: &str) -> Option<Fingerprint> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != 6 {
+        return None;
+    }
+    let field = |i: usize, name: &str| fields[i].strip_prefix(name)?.strip_prefix('=');
+    let (min_width, max_width) = field(4, "width")?.split_once('-')?;
+    Some(Fingerprint {
+        input: String::from(fields[0]),
+        hash: u64::from_str_radix(fields[1], 16).ok()?,
+        lines: field(2, "lines")?.parse().ok()?,
+        blank_lines: field(3, "blank")?.parse().ok()?,
+        min_width: min_width.parse().ok()?,
+        max_width: max_width.parse().ok()?,
+        chars: String::from(field(5, "chars")?),
+    })
+}
+
+pub fn format_manifest(fingerprints: &[Fingerprint]) -> String {
+    let mut contents = String::from(
+        "# <input file> <hash> lines=<N> blank=<N> width=<min>-<max> chars=<classes>\n",
+    );
+    for fingerprint in fingerprints {
+        contents.push_str(&format!("{}\n", fingerprint));
+    }
+    contents
+}
+
+/// The inputs worth fingerprinting: `input.txt` and the sample inputs.
+pub fn input_files(day: u32) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(days::day_dir(day))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|name| {
+                    name == "input.txt" || (name.starts_with("sample") && name.ends_with(".txt"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Fingerprints the day's current inputs.
+pub fn fingerprint_day(day: u32) -> Result<Vec<Fingerprint>, String> {
+    input_files(day)
+        .into_iter()
+        .map(|input| {
+            let path = days::day_dir(day).join(&input);
+            let contents =
+                fs::read(&path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+            Ok(Fingerprint::of(&input, &contents))
+        })
+        .collect()
+}
+
+/// Records the day's current inputs in its `fingerprints.txt`.
+pub fn save_manifest(day: u32) -> Result<Vec<Fingerprint>, String> {
+    let fingerprints = fingerprint_day(day)?;
+    let path = manifest_path(day);
+    fs::write(&path, format_manifest(&fingerprints))
+        .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+    Ok(fingerprints)
+}
+
+/// Compares one of the day's inputs with its recorded fingerprint, returning
+/// a warning if it no longer matches. Inputs that were never fingerprinted,
+/// or that can't be read, aren't warned about here.
+pub fn check_input(day: u32, input: &str) -> Option<String> {
+    match read_manifest(day) {
+        Ok(fingerprints) => compare(day, fingerprints.iter().find(|f| f.input == input)?),
+        Err(message) => Some(message),
+    }
+}
+
+/// Warnings for every fingerprinted input of the day that no longer matches.
+pub fn check_day(day: u32) -> Vec<String> {
+    match read_manifest(day) {
+        Ok(fingerprints) => fingerprints
+            .iter()
+            .filter_map(|f| compare(day, f))
+            .collect(),
+        Err(message) => vec![message],
+    }
+}
+
+fn compare(day: u32, recorded: &Fingerprint) -> Option<String> {
+    let contents = fs::read(days::day_dir(day).join(&recorded.input)).ok()?;
+    let differences = Fingerprint::of(&recorded.input, &contents).differences(recorded);
+    if differences.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Day {} {} doesn't match its recorded fingerprint: {}",
+            day,
+            recorded.input,
+            differences.join("; ")
+        ))
+    }
+}
+
+#[test]
+fn test_fingerprint() {
+    let fingerprint = Fingerprint::of("input.txt", b"abc: 12\n\nX\\y\n");
+    assert_eq!(
+        fingerprint.to_string(),
+        format!(
+            "input.txt {:016x} lines=3 blank=1 width=3-7 chars=0-9a-zA-Z\\s:\\\\",
+            fingerprint.hash
+        )
+    );
+    assert_eq!(Fingerprint::of("empty.txt", b"").chars, "-");
+    assert_ne!(
+        Fingerprint::of("a", b"12\n").hash,
+        Fingerprint::of("a", b"21\n").hash
+    );
+}
+
+#[test]
+fn test_differences() {
+    let recorded = Fingerprint::of("input.txt", b"L.L\n.LL\n");
+    assert!(recorded.differences(&recorded).is_empty());
+    assert_eq!(
+        Fingerprint::of("input.txt", b"LL.\n.LL\n").differences(&recorded),
+        vec!["different contents"]
+    );
+    assert_eq!(
+        Fingerprint::of("input.txt", b"L.L#\n.LL\n\n").differences(&recorded),
+        vec![
+            "3 lines instead of 2",
+            "1 blank lines instead of 0",
+            "lines 3-4 characters wide instead of 3-3",
+            "uses A-Z#. instead of A-Z."
+        ]
+    );
+}
+
+#[test]
+fn test_parse_manifest() {
+    let fingerprints = vec![
+        Fingerprint::of("input.txt", b"1721\n979\n"),
+        Fingerprint::of("sample_input.txt", b"a b\n\nc\n"),
+    ];
+    assert_eq!(
+        parse_manifest(&format_manifest(&fingerprints)),
+        Ok(fingerprints)
+    );
+    assert!(parse_manifest("input.txt 12ab lines=2 blank=0 width=3-4").is_err());
+    assert!(parse_manifest("input.txt xyz lines=2 blank=0 width=3-4 chars=0-9").is_err());
+    assert!(parse_manifest("input.txt 12ab lines=2 blank=0 width=3 chars=0-9").is_err());
+}
+
+#[test]
+fn test_every_input_matches_its_fingerprint() {
+    for day in days::DAYS {
+        assert!(!read_manifest(day).unwrap().is_empty());
+        assert_eq!(check_day(day), Vec::<String>::new());
+    }
+}
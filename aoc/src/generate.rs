@@ -0,0 +1,811 @@
+use aoc_common::Rng;
+use std::collections::HashMap;
+
+/// A generated puzzle input, with answers that are known from the way it was
+/// built rather than by solving it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    // `name=value` settings the input needs, like a shorter preamble
+    pub overrides: Vec<String>,
+    // Day 13's part 2 only works on the real input, so it has no answer here
+    pub answers: Vec<(u32, String)>,
+}
+
+impl Generated {
+    fn new(lines: Vec<String>, part1: impl ToString, part2: impl ToString) -> Generated {
+        Generated {
+            input: lines.iter().map(|l| format!("{}\n", l)).collect(),
+            overrides: Vec::new(),
+            answers: vec![(1, part1.to_string()), (2, part2.to_string())],
+        }
+    }
+
+    pub fn parts(&self) -> Vec<u32> {
+        self.answers.iter().map(|(part, _)| *part).collect()
+    }
+}
+
+/// Generates an input for the day. The same seed always gives the same input.
+pub fn generate(day: u32, rng: &mut Rng) -> Result<Generated, String> {
+    Ok(match day {
+        1 => expense_report(rng),
+        2 => passwords(rng),
+        3 => tree_map(rng),
+        4 => passports(rng),
+        5 => boarding_passes(rng),
+        6 => customs_answers(rng),
+        7 => bag_rules(rng),
+        8 => boot_code(rng),
+        9 => xmas_data(rng),
+        10 => adapters(rng),
+        11 => seat_layout(rng),
+        12 => navigation(rng),
+        13 => bus_notes(rng),
+        14 => docking_program(rng),
+        15 => memory_game(rng),
+        16 => ticket_notes(rng),
+        _ => return Err(format!("No generator for day {}", day)),
+    })
+}
+
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.below(26) as u8) as char
+}
+
+/// Exactly one pair and one triplet of entries add up to the target. Entries
+/// are either small (below half the target) or large (above it), and the
+/// large ones are picked so they can't complete any other sum.
+fn expense_report(rng: &mut Rng) -> Generated {
+    let target = rng.between(100, 4000);
+    let half = (target - 1) / 2;
+    let (pair, triplet) = loop {
+        let a = rng.between(1, half);
+        let x = rng.between(1, half);
+        let y = rng.between(1, half);
+        let z = match target.checked_sub(x + y) {
+            Some(z) if (1..=half).contains(&z) => z,
+            _ => continue,
+        };
+        let small = [a, x, y, z];
+        let distinct = (0..4).all(|i| (i + 1..4).all(|j| small[i] != small[j]));
+        // Only x + y + z may add up to the target using small entries, and no
+        // two of them (or one twice) may add up to a, which pairs with b
+        let mut triplets = 0;
+        let mut completes_a = false;
+        for i in 0..4 {
+            for j in i..4 {
+                completes_a |= small[i] + small[j] == a;
+                triplets += (j..4)
+                    .filter(|k| small[i] + small[j] + small[*k] == target)
+                    .count();
+            }
+        }
+        if distinct && triplets == 1 && !completes_a {
+            break ((a, target - a), (x, y, z));
+        }
+    };
+    let small = [pair.0, triplet.0, triplet.1, triplet.2];
+    let mut forbidden = vec![pair.1];
+    for i in 0..4 {
+        forbidden.push(target - small[i]);
+        for j in i..4 {
+            forbidden.extend(target.checked_sub(small[i] + small[j]));
+        }
+    }
+    let mut large: Vec<u64> = (target / 2 + 1..target)
+        .filter(|n| !forbidden.contains(n))
+        .collect();
+    rng.shuffle(&mut large);
+    large.truncate(rng.between(0, 200) as usize);
+
+    let mut entries: Vec<u64> = small.to_vec();
+    entries.push(pair.1);
+    entries.extend(large);
+    rng.shuffle(&mut entries);
+    let mut generated = Generated::new(
+        entries.iter().map(u64::to_string).collect(),
+        pair.0 * pair.1,
+        triplet.0 * triplet.1 * triplet.2,
+    );
+    generated.overrides.push(format!("target={}", target));
+    generated
+}
+
+/// Each password is built to pass or fail each policy: the letters at the two
+/// positions decide part 2, and the count of the letter elsewhere part 1.
+fn passwords(rng: &mut Rng) -> Generated {
+    let mut lines = Vec::new();
+    let (mut valid1, mut valid2) = (0, 0);
+    for _ in 0..rng.between(1, 200) {
+        let c = letter(rng);
+        let len = rng.between(2, 20) as usize;
+        let first = rng.between(1, len as u64 - 1) as usize;
+        let second = rng.between(first as u64 + 1, len as u64) as usize;
+        let (at_first, at_second) = (rng.chance(1, 2), rng.chance(1, 2));
+        let fixed = at_first as usize + at_second as usize;
+        let want_valid = rng.chance(1, 2);
+        let counts: Vec<usize> = (fixed..=fixed + len - 2)
+            .filter(|n| (first..=second).contains(n) == want_valid)
+            .collect();
+        let count = if counts.is_empty() {
+            fixed
+        } else {
+            *rng.choose(&counts)
+        };
+
+        let mut password: Vec<char> = (0..len)
+            .map(|_| (b'a' + ((c as u8 - b'a') + rng.between(1, 25) as u8) % 26) as char)
+            .collect();
+        if at_first {
+            password[first - 1] = c;
+        }
+        if at_second {
+            password[second - 1] = c;
+        }
+        let mut others: Vec<usize> = (0..len)
+            .filter(|i| *i != first - 1 && *i != second - 1)
+            .collect();
+        rng.shuffle(&mut others);
+        for i in &others[..count - fixed] {
+            password[*i] = c;
+        }
+
+        valid1 += (first..=second).contains(&count) as u32;
+        valid2 += (at_first != at_second) as u32;
+        let password: String = password.into_iter().collect();
+        lines.push(format!("{}-{} {}: {}", first, second, c, password));
+    }
+    Generated::new(lines, valid1, valid2)
+}
+
+/// A random forest, with the trees counted along each of `config.txt`'s slopes
+/// as the map is drawn.
+fn tree_map(rng: &mut Rng) -> Generated {
+    let width = rng.between(1, 40) as usize;
+    let height = rng.between(1, 60) as usize;
+    let map: Vec<Vec<bool>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.chance(1, 4)).collect())
+        .collect();
+    let trees = |right: usize, down: usize| {
+        (1..)
+            .map(|step| (step * down, step * right % width))
+            .take_while(|(row, _)| *row < height)
+            .filter(|(row, column)| map[*row][*column])
+            .count()
+    };
+    let part2: usize = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+        .iter()
+        .map(|(right, down)| trees(*right, *down))
+        .product();
+    let lines = map
+        .iter()
+        .map(|row| row.iter().map(|t| if *t { '#' } else { '.' }).collect())
+        .collect();
+    Generated::new(lines, trees(3, 1), part2)
+}
+
+const PASSPORT_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+fn passport_value(field: &str, valid: bool, rng: &mut Rng) -> String {
+    let year = |rng: &mut Rng, low, high, too_low, too_high| match valid {
+        true => rng.between(low, high).to_string(),
+        false if rng.chance(1, 2) => rng.between(too_low, low - 1).to_string(),
+        false => rng.between(high + 1, too_high).to_string(),
+    };
+    let hex = |rng: &mut Rng, n| -> String {
+        (0..n)
+            .map(|_| *rng.choose(b"0123456789abcdef") as char)
+            .collect()
+    };
+    match (field, valid) {
+        ("byr", _) => year(rng, 1920, 2002, 1900, 2020),
+        ("iyr", _) => year(rng, 2010, 2020, 2000, 2030),
+        ("eyr", _) => year(rng, 2020, 2030, 2010, 2040),
+        ("hgt", true) if rng.chance(1, 2) => format!("{}cm", rng.between(150, 193)),
+        ("hgt", true) => format!("{}in", rng.between(59, 76)),
+        ("hgt", false) => match rng.below(3) {
+            0 => format!("{}cm", rng.between(194, 250)),
+            1 => format!("{}in", rng.between(40, 58)),
+            _ => rng.between(150, 193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(rng, 6)),
+        ("hcl", false) => match rng.below(3) {
+            0 => hex(rng, 6),
+            1 => format!("#{}", hex(rng, 5)),
+            _ => format!("#{}z", hex(rng, 5)),
+        },
+        ("ecl", true) => {
+            String::from(*rng.choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]))
+        }
+        ("ecl", false) => String::from(*rng.choose(&["red", "xyz", "gmt", "lzr"])),
+        ("pid", true) => format!("{:09}", rng.below(1_000_000_000)),
+        ("pid", false) if rng.chance(1, 2) => format!("{:08}", rng.below(100_000_000)),
+        ("pid", false) => format!("{:010}", rng.below(10_000_000_000)),
+        _ => rng.between(1, 999).to_string(),
+    }
+}
+
+/// Each passport is built to be missing fields, to have every field with at
+/// least one invalid, or to be entirely valid.
+fn passports(rng: &mut Rng) -> Generated {
+    let mut records = Vec::new();
+    let (mut complete, mut valid) = (0, 0);
+    for _ in 0..rng.between(1, 100) {
+        let kind = rng.below(3);
+        let mut invalid: Vec<usize> = (0..PASSPORT_FIELDS.len()).collect();
+        rng.shuffle(&mut invalid);
+        invalid.truncate(if kind == 1 {
+            rng.between(1, 3) as usize
+        } else {
+            0
+        });
+        let mut fields: Vec<String> = PASSPORT_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let value = passport_value(field, !invalid.contains(&i), rng);
+                format!("{}:{}", field, value)
+            })
+            .collect();
+        rng.shuffle(&mut fields);
+        if kind == 0 {
+            fields.truncate(PASSPORT_FIELDS.len() - rng.between(1, 3) as usize);
+        }
+        if rng.chance(1, 2) {
+            fields.push(format!("cid:{}", rng.between(1, 999)));
+            rng.shuffle(&mut fields);
+        }
+        complete += (kind != 0) as u32;
+        valid += (kind == 2) as u32;
+
+        let mut lines = vec![Vec::new()];
+        for field in fields {
+            if !lines.last().unwrap().is_empty() && rng.chance(1, 3) {
+                lines.push(Vec::new());
+            }
+            lines.last_mut().unwrap().push(field);
+        }
+        records.push(
+            lines
+                .iter()
+                .map(|l| l.join(" "))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+    }
+    Generated::new(vec![records.join("\n\n")], complete, valid)
+}
+
+/// Every seat ID in a range but one, which is part 2's answer.
+fn boarding_passes(rng: &mut Rng) -> Generated {
+    let len = rng.between(3, 1024);
+    let low = rng.between(0, 1024 - len);
+    let high = low + len - 1;
+    let missing = rng.between(low + 1, high - 1);
+    let mut lines: Vec<String> = (low..=high)
+        .filter(|id| *id != missing)
+        .map(|id| {
+            (0..10)
+                .rev()
+                .map(|bit| match (id >> bit & 1, bit) {
+                    (1, 3..) => 'B',
+                    (_, 3..) => 'F',
+                    (1, _) => 'R',
+                    _ => 'L',
+                })
+                .collect()
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    Generated::new(lines, high, missing)
+}
+
+/// Each group has some questions everyone answered, and others answered by
+/// some but not all of its people.
+fn customs_answers(rng: &mut Rng) -> Generated {
+    let mut groups = Vec::new();
+    let (mut anyone, mut everyone) = (0, 0);
+    for _ in 0..rng.between(1, 100) {
+        let people = rng.between(1, 5) as usize;
+        let mut questions: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut questions);
+        let common = rng.between(if people == 1 { 1 } else { 0 }, 8) as usize;
+        let some = if people == 1 {
+            0
+        } else {
+            rng.between(0, 8) as usize
+        };
+        let mut answers: Vec<Vec<char>> = vec![questions[..common].to_vec(); people];
+        for question in &questions[common..common + some] {
+            // Anyone but the person who's sure to skip it
+            let skipped_by = rng.index(people);
+            for (i, person) in answers.iter_mut().enumerate() {
+                if (i != skipped_by && rng.chance(1, 2)) || i == (skipped_by + 1) % people {
+                    person.push(*question);
+                }
+            }
+        }
+        // Nobody answers nothing; people with no answers yet get one of their own
+        let mut answered = common + some;
+        for person in answers.iter_mut().filter(|a| a.is_empty()) {
+            person.push(questions[answered]);
+            answered += 1;
+        }
+        anyone += answered;
+        everyone += common;
+        for person in answers.iter_mut() {
+            rng.shuffle(person);
+        }
+        groups.push(
+            answers
+                .iter()
+                .map(|a| a.iter().collect::<String>())
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+    }
+    Generated::new(vec![groups.join("\n\n")], anyone, everyone)
+}
+
+const BAG_ADJECTIVES: [&str; 12] = [
+    "bright", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "muted", "pale", "plaid",
+    "vibrant",
+];
+const BAG_COLORS: [&str; 12] = [
+    "black", "blue", "coral", "gold", "green", "lime", "olive", "orange", "red", "tan", "teal",
+    "white",
+];
+
+/// Rules forming a DAG: each color only holds colors after it in a random
+/// order, so the holders and contents of a shiny gold bag can be totted up
+/// from the end of the order as it's built.
+fn bag_rules(rng: &mut Rng) -> Generated {
+    let mut colors = vec![String::from("shiny gold")];
+    for adjective in &BAG_ADJECTIVES {
+        for color in &BAG_COLORS {
+            colors.push(format!("{} {}", adjective, color));
+        }
+    }
+    rng.shuffle(&mut colors);
+    colors.truncate(rng.between(1, 30) as usize);
+    if !colors.iter().any(|c| c == "shiny gold") {
+        let i = rng.index(colors.len());
+        colors[i] = String::from("shiny gold");
+    }
+
+    let target = colors.iter().position(|c| c == "shiny gold").unwrap();
+    // How many bags each color holds, kept small since day 7 explores every
+    // path through the rules
+    let mut inside = vec![0; colors.len()];
+    let mut holds_target = vec![false; colors.len()];
+    let mut lines = Vec::new();
+    for i in (0..colors.len()).rev() {
+        let mut contents = Vec::new();
+        for _ in 0..rng.below(4) {
+            if i + 1 == colors.len() {
+                break;
+            }
+            let j = rng.between(i as u64 + 1, colors.len() as u64 - 1) as usize;
+            let count = rng.between(1, 5);
+            let total = inside[i] + count * (1 + inside[j]);
+            if contents.iter().any(|(k, _)| *k == j) || total > 2000 {
+                continue;
+            }
+            inside[i] = total;
+            holds_target[i] |= j == target || holds_target[j];
+            contents.push((j, count));
+        }
+        let contents: Vec<String> = contents
+            .iter()
+            .map(|(j, count)| {
+                let bags = if *count == 1 { "bag" } else { "bags" };
+                format!("{} {} {}", count, colors[*j], bags)
+            })
+            .collect();
+        if contents.is_empty() {
+            lines.push(format!("{} bags contain no other bags.", colors[i]));
+        } else {
+            lines.push(format!(
+                "{} bags contain {}.",
+                colors[i],
+                contents.join(", ")
+            ));
+        }
+    }
+    rng.shuffle(&mut lines);
+    let holders = holds_target.iter().filter(|h| **h).count();
+    Generated::new(lines, holders, inside[target])
+}
+
+/// A program that runs forward to its end, jumping over dead code that jumps
+/// back, with one of its jumps corrupted into a `nop` so it falls into the
+/// dead code and loops. Its `nop`s also point backwards, so fixing anything
+/// else still loops.
+fn boot_code(rng: &mut Rng) -> Generated {
+    loop {
+        let len = rng.between(5, 80) as usize;
+        let mut program: Vec<String> = Vec::new();
+        // The accumulator at each forward jump, and at the end
+        let mut jumps = Vec::new();
+        let mut accumulator = 0;
+        while program.len() < len {
+            let at = program.len();
+            let over = rng.between(3, 5) as usize;
+            match rng.below(4) {
+                0 => program.push(format!("nop {:+}", -(rng.between(0, at as u64) as i64))),
+                1 if at + over <= len => {
+                    jumps.push((at, accumulator));
+                    program.push(format!("jmp +{}", over));
+                    for dead in at + 1..at + over {
+                        let back = rng.between(dead as u64 - at as u64, dead as u64);
+                        program.push(format!("jmp -{}", back));
+                    }
+                }
+                _ => {
+                    let arg = rng.between(0, 198) as i64 - 99;
+                    accumulator += arg;
+                    program.push(format!("acc {:+}", arg));
+                }
+            }
+        }
+        if jumps.is_empty() {
+            continue;
+        }
+        let (corrupted, before) = *rng.choose(&jumps);
+        program[corrupted] = program[corrupted].replace("jmp", "nop");
+        return Generated::new(program, before, accumulator);
+    }
+}
+
+/// Every number after the preamble is the sum of two in its window, until one
+/// that's the sum of a planted contiguous run instead. Runs that would end
+/// earlier, or a pair that also adds up to it, mean trying again.
+fn xmas_data(rng: &mut Rng) -> Generated {
+    loop {
+        let preamble = rng.between(2, 25) as usize;
+        let mut numbers: Vec<u64> = (0..preamble).map(|_| rng.between(1, 50)).collect();
+        let extend = |numbers: &mut Vec<u64>, rng: &mut Rng| {
+            let window = numbers.len() - preamble;
+            let a = rng.between(window as u64, numbers.len() as u64 - 1) as usize;
+            let b = loop {
+                let b = rng.between(window as u64, numbers.len() as u64 - 1) as usize;
+                if b != a {
+                    break b;
+                }
+            };
+            numbers.push(numbers[a] + numbers[b]);
+        };
+        for _ in 0..rng.between(1, 60) {
+            if *numbers.last().unwrap() > 1_000_000_000_000 {
+                break;
+            }
+            extend(&mut numbers, rng);
+        }
+
+        let start = rng.index(numbers.len() - 1);
+        let end = rng.between(start as u64 + 1, numbers.len() as u64 - 1) as usize;
+        let run = &numbers[start..=end];
+        let invalid: u64 = run.iter().sum();
+        let window = &numbers[numbers.len() - preamble..];
+        let is_pair_sum = window
+            .iter()
+            .any(|a| window.iter().any(|b| a + b == invalid));
+        let ends_earlier = (0..end).any(|e| {
+            (0..=e)
+                .rev()
+                .scan(0, |sum, s| {
+                    *sum += numbers[s];
+                    Some(*sum)
+                })
+                .any(|sum| sum == invalid)
+        });
+        if is_pair_sum || ends_earlier {
+            continue;
+        }
+        let weakness = run.iter().min().unwrap() + run.iter().max().unwrap();
+        numbers.push(invalid);
+        for _ in 0..rng.below(4) {
+            extend(&mut numbers, rng);
+        }
+        let mut generated = Generated::new(
+            numbers.iter().map(u64::to_string).collect(),
+            invalid,
+            weakness,
+        );
+        generated.overrides.push(format!("preamble={}", preamble));
+        return generated;
+    }
+}
+
+/// Runs of up to four 1-jolt steps between 3-jolt steps. A run of n steps can
+/// be crossed in 1, 2, 4 or 7 ways.
+fn adapters(rng: &mut Rng) -> Generated {
+    let (mut ones, mut threes, mut arrangements): (u64, u64, u64) = (0, 1, 1);
+    let mut joltage = 0;
+    let mut adapters = Vec::new();
+    for _ in 0..rng.between(1, 20) {
+        let run = rng.between(1, 4);
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        ones += run;
+        arrangements *= [1, 2, 4, 7][run as usize - 1];
+        for _ in 0..rng.between(1, 2) {
+            joltage += 3;
+            adapters.push(joltage);
+            threes += 1;
+        }
+    }
+    rng.shuffle(&mut adapters);
+    Generated::new(
+        adapters.iter().map(u64::to_string).collect(),
+        ones * threes,
+        arrangements,
+    )
+}
+
+/// No two seats share a row or column, so none has more than two neighbours
+/// or can see more than four others, and every seat ends up occupied.
+fn seat_layout(rng: &mut Rng) -> Generated {
+    let height = rng.between(1, 30) as usize;
+    let width = rng.between(1, 30) as usize;
+    let mut rows: Vec<usize> = (0..height).collect();
+    let mut columns: Vec<usize> = (0..width).collect();
+    rng.shuffle(&mut rows);
+    rng.shuffle(&mut columns);
+    let seats = rng.between(0, height.min(width) as u64) as usize;
+    let mut layout = vec![vec!['.'; width]; height];
+    for (row, column) in rows.iter().zip(&columns).take(seats) {
+        layout[*row][*column] = 'L';
+    }
+    Generated::new(
+        layout.iter().map(|row| row.iter().collect()).collect(),
+        seats,
+        seats,
+    )
+}
+
+/// Random instructions, with the ship and its waypoint followed as vectors.
+fn navigation(rng: &mut Rng) -> Generated {
+    // East and north
+    let (mut ship1, mut heading) = ((0_i64, 0_i64), (1_i64, 0_i64));
+    let (mut ship2, mut waypoint) = ((0_i64, 0_i64), (10_i64, 1_i64));
+    let mut lines = Vec::new();
+    for _ in 0..rng.between(1, 100) {
+        let action = *rng.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F']);
+        let value = match action {
+            'L' | 'R' => rng.between(1, 3) as i64 * 90,
+            _ => rng.between(1, 100) as i64,
+        };
+        lines.push(format!("{}{}", action, value));
+        let step = match action {
+            'N' => (0, value),
+            'S' => (0, -value),
+            'E' => (value, 0),
+            'W' => (-value, 0),
+            _ => (0, 0),
+        };
+        ship1 = (ship1.0 + step.0, ship1.1 + step.1);
+        waypoint = (waypoint.0 + step.0, waypoint.1 + step.1);
+        // Quarter turns to the right
+        let turns = match action {
+            'R' => value / 90,
+            'L' => 4 - value / 90,
+            _ => 0,
+        };
+        for _ in 0..turns {
+            heading = (heading.1, -heading.0);
+            waypoint = (waypoint.1, -waypoint.0);
+        }
+        if action == 'F' {
+            ship1 = (ship1.0 + heading.0 * value, ship1.1 + heading.1 * value);
+            ship2 = (ship2.0 + waypoint.0 * value, ship2.1 + waypoint.1 * value);
+        }
+    }
+    let distance = |(east, north): (i64, i64)| east.unsigned_abs() + north.unsigned_abs();
+    Generated::new(lines, distance(ship1), distance(ship2))
+}
+
+/// One bus is planted to leave soonest after the earliest departure; the rest
+/// leave later. Part 2 is left out since day 13 only solves the real input.
+fn bus_notes(rng: &mut Rng) -> Generated {
+    let best = rng.between(2, 999);
+    let wait = rng.between(0, best - 1);
+    let earliest = rng.between(1, 1_000_000 / best + 1) * best - wait;
+    let mut schedule = vec![best.to_string()];
+    for _ in 0..rng.between(0, 9) {
+        let bus = rng.between(2, 999);
+        if (bus - earliest % bus) % bus > wait {
+            schedule.push(bus.to_string());
+        }
+    }
+    for _ in 0..rng.between(0, 20) {
+        schedule.push(String::from("x"));
+    }
+    rng.shuffle(&mut schedule);
+    let mut generated = Generated::new(vec![earliest.to_string(), schedule.join(",")], 0, 0);
+    generated.answers = vec![(1, (best * wait).to_string())];
+    generated
+}
+
+/// Random masks and writes, with memory followed in integers rather than the
+/// strings day 14 uses. Masks have few enough `X`s to keep part 2 small.
+fn docking_program(rng: &mut Rng) -> Generated {
+    let (mut mem1, mut mem2) = (HashMap::new(), HashMap::new());
+    let (mut ones, mut floating) = (0, 0);
+    let mut lines = Vec::new();
+    for i in 0..rng.between(1, 60) {
+        if i == 0 || rng.chance(1, 4) {
+            let mut bits: Vec<u64> = (0..36).collect();
+            rng.shuffle(&mut bits);
+            let xs = rng.between(0, 6) as usize;
+            floating = bits[..xs].iter().map(|b| 1 << b).sum();
+            ones = bits[xs..]
+                .iter()
+                .filter(|_| rng.chance(1, 2))
+                .map(|b| 1 << b)
+                .sum();
+            let mask: String = (0..36)
+                .rev()
+                .map(|bit| match (floating >> bit & 1, ones >> bit & 1) {
+                    (1, _) => 'X',
+                    (_, 1) => '1',
+                    _ => '0',
+                })
+                .collect();
+            lines.push(format!("mask = {}", mask));
+            continue;
+        }
+        let addr = rng.between(0, 65535);
+        let value = rng.between(0, (1 << 36) - 1);
+        lines.push(format!("mem[{}] = {}", addr, value));
+        let zeros = !floating & !ones & ((1 << 36) - 1);
+        mem1.insert(addr, value & !zeros | ones);
+        // Every subset of the floating bits, counting down through them
+        let base = (addr | ones) & !floating;
+        let mut subset = floating;
+        loop {
+            mem2.insert(base | subset, value);
+            if subset == 0 {
+                break;
+            }
+            subset = (subset - 1) & floating;
+        }
+    }
+    Generated::new(
+        lines,
+        mem1.values().sum::<u64>(),
+        mem2.values().sum::<u64>(),
+    )
+}
+
+/// A short game, played with a table of when each number was last spoken.
+fn memory_game(rng: &mut Rng) -> Generated {
+    let mut starting: Vec<u64> = (0..=20).collect();
+    rng.shuffle(&mut starting);
+    starting.truncate(rng.between(1, 7) as usize);
+    let play = |turns: usize| {
+        let mut last_spoken = vec![None; turns.max(21)];
+        let mut spoken = starting[0];
+        for turn in 1..turns {
+            let next = match starting.get(turn) {
+                Some(n) => *n,
+                None => last_spoken[spoken as usize].map_or(0, |t| (turn - t) as u64),
+            };
+            last_spoken[spoken as usize] = Some(turn);
+            spoken = next;
+        }
+        spoken
+    };
+    let part1_turns = rng.between(starting.len() as u64, 3000) as usize;
+    let part2_turns = rng.between(starting.len() as u64, 30_000) as usize;
+    let line = starting
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<String>>()
+        .join(",");
+    let mut generated = Generated::new(vec![line], play(part1_turns), play(part2_turns));
+    generated.overrides = vec![
+        format!("part1_turns={}", part1_turns),
+        format!("part2_turns={}", part2_turns),
+    ];
+    generated
+}
+
+const TICKET_FIELDS: [&str; 16] = [
+    "cabin", "car", "class", "date", "duration", "gate", "location", "platform", "price", "route",
+    "row", "seat", "time", "track", "wagon", "zone",
+];
+
+/// Fields whose ranges nest: the field ranked k accepts up to 100 * (k + 1),
+/// and each position holds a value only the fields ranked at or above its own
+/// accept. Ruling out fields then pins down one position at a time. Each
+/// field's range also has a one-value gap that no ticket uses.
+fn ticket_notes(rng: &mut Rng) -> Generated {
+    let mut names: Vec<&str> = TICKET_FIELDS.to_vec();
+    rng.shuffle(&mut names);
+    let count = rng.between(1, 12) as usize;
+    let departures = rng.between(1, count.min(4) as u64) as usize;
+    let names: Vec<String> = names[..count]
+        .iter()
+        .enumerate()
+        .map(|(rank, name)| {
+            if rank < departures {
+                format!("departure {}", name)
+            } else {
+                String::from(*name)
+            }
+        })
+        .collect();
+    let mut ranks: Vec<usize> = (0..count).collect();
+    rng.shuffle(&mut ranks);
+    let gaps: Vec<u64> = (0..count).map(|_| rng.between(2, 99)).collect();
+
+    let mut rules: Vec<String> = (0..count)
+        .map(|rank| {
+            let (gap, high) = (gaps[rank], 100 * (rank as u64 + 1));
+            format!("{}: 1-{} or {}-{}", names[rank], gap - 1, gap + 1, high)
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+    // A value the position's field accepts; with `exclude`, one that rules
+    // out the fields ranked below it too
+    let value = |rng: &mut Rng, rank: usize, exclude: bool| loop {
+        let low = if exclude { 100 * rank as u64 + 1 } else { 1 };
+        let value = rng.between(low, 100 * (rank as u64 + 1));
+        if !gaps.contains(&value) {
+            break value;
+        }
+    };
+    let ticket = |rng: &mut Rng, exclude: bool| -> Vec<u64> {
+        ranks
+            .iter()
+            .map(|rank| value(rng, *rank, exclude))
+            .collect()
+    };
+
+    let mine = ticket(rng, false);
+    let product: u64 = ranks
+        .iter()
+        .zip(&mine)
+        .filter(|(rank, _)| **rank < departures)
+        .map(|(_, value)| value)
+        .product();
+    let mut error_rate = 0;
+    let mut nearby = vec![ticket(rng, true)];
+    for _ in 0..rng.between(0, 30) {
+        let mut ticket = ticket(rng, false);
+        if rng.chance(1, 3) {
+            let invalid = rng.between(100 * count as u64 + 1, 100 * count as u64 + 900);
+            ticket[rng.index(count)] = invalid;
+            error_rate += invalid;
+        }
+        nearby.push(ticket);
+    }
+    rng.shuffle(&mut nearby);
+
+    let join = |ticket: &Vec<u64>| {
+        ticket
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    let mut lines = rules;
+    lines.extend([String::new(), String::from("your ticket:"), join(&mine)]);
+    lines.extend([String::new(), String::from("nearby tickets:")]);
+    lines.extend(nearby.iter().map(join));
+    Generated::new(lines, error_rate, product)
+}
+
+#[test]
+fn test_same_seed_same_input() {
+    for day in crate::days::DAYS {
+        let first = generate(day, &mut Rng::new(3)).unwrap();
+        assert_eq!(first, generate(day, &mut Rng::new(3)).unwrap());
+        assert_ne!(first.input, generate(day, &mut Rng::new(4)).unwrap().input);
+    }
+    assert!(generate(17, &mut Rng::new(3)).is_err());
+}
@@ -0,0 +1,14 @@
+//! Runs any day's solution from a single binary.
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod days;
+pub mod fingerprint;
+pub mod generate;
+pub mod pool;
+pub mod repl;
+pub mod scaffold;
+pub mod server;
+pub mod summary;
+pub mod watch;
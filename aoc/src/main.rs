@@ -0,0 +1,358 @@
+use aoc::answers;
+use aoc::bench;
+use aoc::cli::{self, BenchOptions, Command, RunOptions, ServeOptions, WatchOptions};
+use aoc::days;
+use aoc::fingerprint;
+use aoc::generate;
+use aoc::pool;
+use aoc::scaffold;
+use aoc::server::Server;
+use aoc::summary;
+use aoc::watch;
+use aoc_common::{alloc, format_report, log, CancelToken, Format, Report, Rng};
+use std::env;
+use std::process;
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Counts allocations so reports include each phase's memory use
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+fn main() {
+    match cli::parse_args(env::args().skip(1)) {
+        Ok(Command::Run(options)) => {
+            log::init(options.verbosity);
+            if !run(&options) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Bench(options)) => {
+            log::init(options.verbosity);
+            if let Err(message) = run_bench(&options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Ok(Command::New(day)) => {
+            if let Err(message) = run_new(day) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Watch(options)) => {
+            if let Err(message) = run_watch(&options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Fingerprint { days, save }) => match run_fingerprint(&days, save) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(message) => {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        },
+        Ok(Command::Serve(options)) => {
+            log::init(options.verbosity);
+            if let Err(message) = run_serve(&options) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Generate { day, seed }) => {
+            if let Err(message) = run_generate(day, seed) {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+// Keeps going after a day fails, so one bad input doesn't hide the other
+// days' answers. Returns whether every day succeeded.
+fn run(options: &RunOptions) -> bool {
+    if options.days.len() > 1 {
+        return run_all(options);
+    }
+    let mut succeeded = true;
+    for &day in &options.days {
+        let input = options
+            .input
+            .clone()
+            .unwrap_or_else(|| days::default_input(day));
+        if options.format == Format::Text {
+            println!("Day {}", day);
+        }
+        if options.input.is_none() {
+            warn_if_changed(day);
+        }
+        let solver = match days::solver(day, &options.overrides) {
+            Ok(solver) => solver,
+            Err(message) => {
+                eprintln!("error: Day {}: {}", day, message);
+                succeeded = false;
+                continue;
+            }
+        };
+        let solved = cancel_token(options)
+            .install(|| solver.solve(&input.to_string_lossy(), &options.parts));
+        match solved {
+            Ok(report) => print!("{}", format_report(day, &report, options.format)),
+            Err(e) => {
+                eprintln!("error: Day {}: {}", day, e);
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+// Runs the days on a pool of worker threads and prints a table comparing the
+// answers with the known ones, or the usual JSON lines. A day that fails or
+// panics is reported without stopping the others. Returns whether every day
+// succeeded with the known answers.
+fn run_all(options: &RunOptions) -> bool {
+    let started = Instant::now();
+    let baselines = bench::read_baselines().unwrap_or_default();
+    let order = bench::slowest_first(&options.days, &baselines);
+    // The allocation counters are shared, so days have to take turns
+    let threads = if alloc::is_counting() {
+        1
+    } else {
+        options.jobs.unwrap_or_else(pool::default_threads)
+    };
+    let outcomes = pool::map_parallel(&order, threads, |&day| {
+        let input = days::default_input(day);
+        let solver = days::solver(day, &[])?;
+        cancel_token(options)
+            .install(|| solver.solve(&input.to_string_lossy(), &options.parts))
+            .map_err(|e| e.to_string())
+    });
+    let wall_time = started.elapsed();
+
+    let mut results: Vec<(u32, Result<Report, String>)> = order
+        .into_iter()
+        .zip(outcomes)
+        .map(|(day, outcome)| {
+            let outcome = outcome.unwrap_or_else(|message| Err(format!("panicked: {}", message)));
+            (day, outcome)
+        })
+        .collect();
+    results.sort_by_key(|(day, _)| *day);
+    for (day, _) in &results {
+        warn_if_changed(*day);
+    }
+
+    if options.format == Format::Json {
+        let mut succeeded = true;
+        for (day, outcome) in &results {
+            match outcome {
+                Ok(report) => print!("{}", format_report(*day, report, options.format)),
+                Err(message) => {
+                    eprintln!("error: Day {}: {}", day, message);
+                    succeeded = false;
+                }
+            }
+        }
+        return succeeded;
+    }
+    let mut rows = Vec::new();
+    for (day, outcome) in &results {
+        let known_answers = answers::read_manifest(*day).unwrap_or_default();
+        rows.extend(summary::day_rows(*day, outcome, &known_answers));
+    }
+    print!("{}", summary::format_table(&rows, wall_time));
+    summary::all_passed(&rows)
+}
+
+// The recorded answers only hold for the input they came from
+fn warn_if_changed(day: u32) {
+    if let Some(warning) = fingerprint::check_input(day, "input.txt") {
+        eprintln!("warning: {}", warning);
+    }
+}
+
+// Each day gets its own token, so its time starts when it does
+fn cancel_token(options: &RunOptions) -> CancelToken {
+    options
+        .timeout
+        .map(CancelToken::with_timeout)
+        .unwrap_or_default()
+}
+
+fn run_bench(options: &BenchOptions) -> Result<(), String> {
+    let baselines = bench::read_baselines()?;
+    let mut results = Vec::new();
+    let mut regressions = Vec::new();
+    for &day in &options.days {
+        let result = bench::bench_day(day, options.runs)?;
+        for (i, phase) in bench::PHASES.iter().enumerate() {
+            match result.peaks[i] {
+                Some(peak_bytes) => println!(
+                    "Day {:>2} {:<5}  {}  peak {:>10}",
+                    day,
+                    phase,
+                    result.phases[i],
+                    alloc::format_bytes(peak_bytes)
+                ),
+                None => println!("Day {:>2} {:<5}  {}", day, phase, result.phases[i]),
+            }
+        }
+        regressions.extend(
+            bench::find_regressions(&result, &baselines, options.threshold)
+                .iter()
+                .map(ToString::to_string),
+        );
+        regressions.extend(
+            bench::find_memory_regressions(&result, &baselines, options.threshold)
+                .iter()
+                .map(ToString::to_string),
+        );
+        results.push(result);
+    }
+    if options.save {
+        bench::save_baselines(&results)?;
+        println!("Saved baselines to {}", bench::baselines_path().display());
+        return Ok(());
+    }
+    for regression in &regressions {
+        println!("{}", regression);
+    }
+    if regressions.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} phase(s) regressed by more than {}%",
+            regressions.len(),
+            options.threshold
+        ))
+    }
+}
+
+// Returns whether every input matched, when checking
+fn run_fingerprint(days: &[u32], save: bool) -> Result<bool, String> {
+    if save {
+        for &day in days {
+            let fingerprints = fingerprint::save_manifest(day)?;
+            println!(
+                "Saved {} fingerprint(s) to {}",
+                fingerprints.len(),
+                fingerprint::manifest_path(day).display()
+            );
+        }
+        return Ok(true);
+    }
+    let mut matched = true;
+    for &day in days {
+        let warnings = fingerprint::check_day(day);
+        if warnings.is_empty() {
+            println!("Day {}: inputs match", day);
+        }
+        for warning in warnings {
+            println!("{}", warning);
+            matched = false;
+        }
+    }
+    Ok(matched)
+}
+
+// The input goes to stdout so it can be redirected to a file; the seed and
+// the planted answers go to stderr.
+fn run_generate(day: u32, seed: Option<u64>) -> Result<(), String> {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    let generated = generate::generate(day, &mut Rng::new(seed))?;
+    print!("{}", generated.input);
+    eprintln!("Seed {}", seed);
+    for assignment in &generated.overrides {
+        eprintln!("Needs --set {}", assignment);
+    }
+    for (part, answer) in &generated.answers {
+        eprintln!("PART {}: {}", part, answer);
+    }
+    Ok(())
+}
+
+fn run_serve(options: &ServeOptions) -> Result<(), String> {
+    let server = Server::bind(options.port, options.limits)?;
+    println!("Listening on http://{}", server.local_addr());
+    server.serve();
+    Ok(())
+}
+
+fn run_new(day: u32) -> Result<(), String> {
+    for path in scaffold::create_day(days::workspace_root(), day)? {
+        println!("Created {}", path.display());
+    }
+    println!(
+        "Add day{} to aoc/Cargo.toml and days::solver to run it with `aoc run`",
+        day
+    );
+    Ok(())
+}
+
+// Runs until interrupted. Build and test failures are reported and then
+// waited out, since the next edit will usually fix them.
+fn run_watch(options: &WatchOptions) -> Result<(), String> {
+    let dir = days::day_dir(options.day);
+    if !dir.is_dir() {
+        return Err(format!("{} doesn't exist", dir.display()));
+    }
+    println!("Watching {} (Ctrl-C to stop)", dir.display());
+    let mut files = watch::snapshot(&dir);
+    let mut answers = None;
+    loop {
+        if let Some(current) = watch_cycle(&dir, options.day, answers.as_deref()) {
+            answers = Some(current);
+        }
+        loop {
+            thread::sleep(options.interval);
+            let latest = watch::snapshot(&dir);
+            let changed = watch::changed(&files, &latest);
+            files = latest;
+            if !changed.is_empty() {
+                for path in changed {
+                    let path = path.strip_prefix(&dir).unwrap_or(&path);
+                    println!("\nChanged: {}", path.display());
+                }
+                break;
+            }
+        }
+    }
+}
+
+// Rebuilds the day, solves its input.txt and runs its sample tests, returning
+// the answers if it got that far
+fn watch_cycle(
+    dir: &std::path::Path,
+    day: u32,
+    previous: Option<&[(u32, String)]>,
+) -> Option<Vec<(u32, String)>> {
+    let outcome = watch::build(dir, day).and_then(|()| watch::solve(dir, day));
+    let answers = match outcome {
+        Ok(answers) => answers,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return None;
+        }
+    };
+    for line in watch::diff_answers(previous, &answers) {
+        println!("{}", line);
+    }
+    match watch::test(dir, day) {
+        Ok(()) => println!("Sample tests passed"),
+        Err(message) => eprintln!("error: {}", message),
+    }
+    Some(answers)
+}
@@ -0,0 +1,77 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// The number of threads to use when none is given: one per CPU.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Calls `work` on every item using `threads` worker threads, which take the
+/// items in order. Results come back in the same order as the items. If
+/// `work` panics, that item's result is an error with the panic message. The
+/// other items still run.
+pub fn map_parallel<T, R, F>(items: &[T], threads: usize, work: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<R, String>>>> =
+        Mutex::new((0..items.len()).map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(i) {
+                    Some(item) => item,
+                    None => break,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(item)))
+                    .map_err(|payload| panic_message(payload.as_ref()));
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.unwrap())
+        .collect()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[test]
+fn test_map_parallel() {
+    let items: Vec<u32> = (1..=20).collect();
+    let results = map_parallel(&items, 4, |n| n * n);
+    let squares: Vec<u32> = results.into_iter().map(|r| r.unwrap()).collect();
+    assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<u32>>());
+
+    assert!(map_parallel(&[] as &[u32], 4, |n| *n).is_empty());
+    assert_eq!(map_parallel(&[3], 0, |n| n + 1), vec![Ok(4)]);
+}
+
+#[test]
+fn test_map_parallel_reports_panics() {
+    let results = map_parallel(&[1, 2, 3], 2, |n| {
+        if *n == 2 {
+            panic!("bad item {}", n);
+        }
+        *n
+    });
+    assert_eq!(results, vec![Ok(1), Err(String::from("bad item 2")), Ok(3)]);
+}
@@ -0,0 +1,374 @@
+use crate::days;
+use aoc_common::Solution;
+use day7::Bag;
+use day8::{Instruction, Machine};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+pub const HELP: &str = "Commands:
+    bags contain \"<color>\"   how many colors can eventually hold a <color> bag
+    bags inside \"<color>\"    how many bags a <color> bag holds
+    bags rule \"<color>\"      what a <color> bag must contain
+    seat <pass>              decode a boarding pass like BFFFBBFRRR
+    vm [step [N] | run | reset]
+                             run day 8's program N instructions at a time
+    spoken <numbers> <turn>  play day 15's game, e.g. spoken 0,3,6 2020
+    load <day> <file>        use another input, e.g. load 7 sample_input.txt
+    history                  list earlier commands; !N repeats one, !! the last
+    help, quit";
+
+/// Evaluates commands against the days' inputs, which are loaded the first
+/// time a command needs them and kept for the rest of the session.
+#[derive(Default)]
+pub struct Repl {
+    // Inputs other than a day's input.txt, chosen with `load`
+    inputs: HashMap<u32, PathBuf>,
+    bags: Option<HashMap<String, Bag>>,
+    program: Option<Vec<Instruction>>,
+    machine: Machine,
+    // The instructions the machine has run, to spot it looping
+    executed: HashSet<usize>,
+    history: Vec<String>,
+}
+
+impl Repl {
+    /// Starts a session that remembers the commands from earlier ones.
+    pub fn with_history(history: Vec<String>) -> Repl {
+        Repl {
+            history,
+            ..Repl::default()
+        }
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Runs a line of input, returning what to print. `!N` and `!!` are
+    /// replaced with the command they refer to, and every command that's
+    /// understood is added to the history.
+    pub fn eval(&mut self, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        let command = match line.strip_prefix('!') {
+            Some("!") => self.history.last().cloned().ok_or("No commands yet")?,
+            Some(n) => {
+                let entry = n.parse::<usize>().ok().and_then(|n| n.checked_sub(1));
+                entry
+                    .and_then(|i| self.history.get(i))
+                    .cloned()
+                    .ok_or_else(|| format!("No command {} in the history", n))?
+            }
+            None => String::from(line),
+        };
+        let words = split_words(&command)?;
+        let output = match words.first().map(String::as_str) {
+            None => return Ok(String::new()),
+            Some("history") => self.list_history(),
+            Some(_) => self.run(&words)?,
+        };
+        if words[0] != "history" {
+            self.history.push(command);
+        }
+        Ok(output)
+    }
+
+    fn run(&mut self, words: &[String]) -> Result<String, String> {
+        let args: Vec<&str> = words[1..].iter().map(String::as_str).collect();
+        match (words[0].as_str(), args.as_slice()) {
+            ("help", []) => Ok(String::from(HELP)),
+            ("bags", [query, color]) => self.bags(query, color),
+            ("seat", [pass]) => {
+                let seat = day5::get_seat(pass)
+                    .ok_or_else(|| format!("'{}' isn't a boarding pass", pass))?;
+                Ok(format!(
+                    "Row {}, column {}, seat ID {}",
+                    seat.row, seat.column, seat.id
+                ))
+            }
+            ("vm", args) => self.vm(args),
+            ("spoken", [numbers, turn]) => {
+                let numbers = day15::to_starting_numbers(numbers).map_err(|e| e.to_string())?;
+                let turn = turn
+                    .parse()
+                    .map_err(|_| format!("'{}' isn't a turn number", turn))?;
+                day15::nth_spoken_number(&numbers, turn)
+                    .map(|n| n.to_string())
+                    .map_err(|e| e.to_string())
+            }
+            ("load", [day, file]) => self.load(day, file),
+            (command, _) => Err(format!(
+                "Don't know how to '{}' with those arguments; try help",
+                command
+            )),
+        }
+    }
+
+    fn list_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, command)| format!("{:>4}  {}", i + 1, command))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn input(&self, day: u32) -> String {
+        let path = self
+            .inputs
+            .get(&day)
+            .cloned()
+            .unwrap_or_else(|| days::default_input(day));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn load(&mut self, day: &str, file: &str) -> Result<String, String> {
+        let day = match day.parse() {
+            Ok(day @ (7 | 8)) => day,
+            _ => return Err(String::from("Only days 7 and 8 have inputs to load")),
+        };
+        let path = days::day_dir(day).join(file);
+        if !path.is_file() {
+            return Err(format!("{} doesn't exist", path.display()));
+        }
+        self.inputs.insert(day, path);
+        // Forget the old input so the next command reads the new one
+        match day {
+            7 => self.bags = None,
+            _ => self.program = None,
+        }
+        Ok(format!("Day {} will use {}", day, file))
+    }
+
+    fn bags(&mut self, query: &str, color: &str) -> Result<String, String> {
+        if self.bags.is_none() {
+            let bags = day7::Day7::default()
+                .parse_file(&self.input(7))
+                .map_err(|e| e.to_string())?;
+            self.bags = Some(bags);
+        }
+        let bags = self.bags.as_ref().unwrap();
+        let bag = day7::get_bag(color, bags).map_err(|e| e.to_string())?;
+        match query {
+            "contain" => {
+                let holders =
+                    day7::count_possible_holders(color, bags).map_err(|e| e.to_string())?;
+                Ok(format!(
+                    "{} bag colors can eventually contain a {} bag",
+                    holders, color
+                ))
+            }
+            "inside" => {
+                let count = day7::contained_bag_count(bag, bags).map_err(|e| e.to_string())?;
+                Ok(format!("A {} bag holds {} other bags", color, count))
+            }
+            "rule" if bag.allowed_bags.is_empty() => {
+                Ok(format!("{} bags contain no other bags", color))
+            }
+            "rule" => {
+                let mut contents: Vec<String> = bag
+                    .allowed_bags
+                    .iter()
+                    .map(|(inner, count)| format!("{} {}", count, inner))
+                    .collect();
+                contents.sort();
+                Ok(format!("{} bags contain {}", color, contents.join(", ")))
+            }
+            _ => Err(format!(
+                "Expected bags contain, inside or rule, got '{}'",
+                query
+            )),
+        }
+    }
+
+    fn vm(&mut self, args: &[&str]) -> Result<String, String> {
+        if self.program.is_none() {
+            let program = day8::Day8
+                .parse_file(&self.input(8))
+                .map_err(|e| e.to_string())?;
+            self.program = Some(program);
+            self.reset_vm();
+        }
+        match args {
+            [] => Ok(self.vm_state()),
+            ["reset"] => {
+                self.reset_vm();
+                Ok(self.vm_state())
+            }
+            ["step"] => self.vm_steps(1, true),
+            ["step", n] => {
+                let n = n
+                    .parse()
+                    .map_err(|_| format!("'{}' isn't a number of steps", n))?;
+                self.vm_steps(n, true)
+            }
+            ["run"] => self.vm_steps(usize::MAX, false),
+            _ => Err(String::from("Expected vm, vm step [N], vm run or vm reset")),
+        }
+    }
+
+    fn reset_vm(&mut self) {
+        self.machine = Machine::default();
+        self.executed.clear();
+    }
+
+    // Stops early, before running an instruction a second time, since the
+    // program would loop forever from there
+    fn vm_steps(&mut self, n: usize, show_each: bool) -> Result<String, String> {
+        let program = self.program.as_ref().unwrap();
+        let mut lines = Vec::new();
+        for _ in 0..n {
+            let at = self.machine.instruction_number;
+            if self.executed.contains(&at) && !self.machine.jumped_before_start {
+                lines.push(format!("Instruction {} would run a second time", at));
+                break;
+            }
+            match self.machine.step(program).map_err(|e| e.to_string())? {
+                Some(instruction) => {
+                    self.executed.insert(at);
+                    if show_each {
+                        lines.push(format!(
+                            "{:>5}: {:<8} acc {}",
+                            at,
+                            instruction.to_string(),
+                            self.machine.accumulator
+                        ));
+                    }
+                }
+                None => break,
+            }
+        }
+        lines.push(self.vm_state());
+        Ok(lines.join("\n"))
+    }
+
+    fn vm_state(&self) -> String {
+        let program = self.program.as_ref().unwrap();
+        let machine = &self.machine;
+        let position = if machine.terminated(program) {
+            String::from("terminated")
+        } else if machine.jumped_before_start {
+            String::from("jumped before the first instruction")
+        } else {
+            match program.get(machine.instruction_number) {
+                Some(next) => format!("next {}: {}", machine.instruction_number, next),
+                None => format!("jumped past the end to {}", machine.instruction_number),
+            }
+        };
+        format!(
+            "acc {} after {} steps, {}",
+            machine.accumulator, machine.steps, position
+        )
+    }
+}
+
+/// Splits a command into words, keeping double-quoted text together.
+pub fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(String::from("Unclosed quote"));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[test]
+fn test_split_words() {
+    assert_eq!(
+        split_words(" bags  contain \"shiny gold\"").unwrap(),
+        vec!["bags", "contain", "shiny gold"]
+    );
+    assert_eq!(split_words("a \"\" b").unwrap(), vec!["a", "", "b"]);
+    assert!(split_words("bags rule \"shiny").is_err());
+}
+
+#[test]
+fn test_commands() {
+    let mut repl = Repl::default();
+    assert_eq!(
+        repl.eval("seat BFFFBBFRRR"),
+        Ok(String::from("Row 70, column 7, seat ID 567"))
+    );
+    assert_eq!(repl.eval("spoken 0,3,6 2020"), Ok(String::from("436")));
+    assert!(repl.eval("seat XYZ").is_err());
+    assert!(repl.eval("fly").is_err());
+
+    repl.eval("load 7 sample_input.txt").unwrap();
+    assert_eq!(
+        repl.eval("bags inside \"shiny gold\""),
+        Ok(String::from("A shiny gold bag holds 126 other bags"))
+    );
+    assert_eq!(
+        repl.eval("bags rule \"shiny gold\""),
+        Ok(String::from("shiny gold bags contain 2 dark red"))
+    );
+    assert!(repl.eval("bags contain \"plaid\"").is_err());
+    assert!(repl.eval("load 9 input.txt").is_err());
+}
+
+#[test]
+fn test_vm() {
+    let mut repl = Repl::default();
+    repl.eval("load 8 sample_input.txt").unwrap();
+    assert_eq!(
+        repl.eval("vm"),
+        Ok(String::from("acc 0 after 0 steps, next 0: nop +0"))
+    );
+    assert_eq!(
+        repl.eval("vm step 2"),
+        Ok(String::from(
+            "    0: nop +0   acc 0\n    1: acc +1   acc 1\nacc 1 after 2 steps, next 2: jmp +4"
+        ))
+    );
+    assert_eq!(
+        repl.eval("vm run"),
+        Ok(String::from(
+            "Instruction 1 would run a second time\nacc 5 after 7 steps, next 1: acc +1"
+        ))
+    );
+    assert_eq!(
+        repl.eval("vm reset"),
+        Ok(String::from("acc 0 after 0 steps, next 0: nop +0"))
+    );
+}
+
+#[test]
+fn test_history() {
+    let mut repl = Repl::with_history(vec![String::from("spoken 0,3,6 10")]);
+    assert_eq!(repl.eval("!!"), Ok(String::from("0")));
+    assert_eq!(
+        repl.eval("seat FFFFFFFLLL"),
+        Ok(String::from("Row 0, column 0, seat ID 0"))
+    );
+    assert_eq!(repl.eval("!1"), Ok(String::from("0")));
+    assert!(repl.eval("!9").is_err());
+    assert_eq!(
+        repl.eval("history"),
+        Ok(String::from(
+            "   1  spoken 0,3,6 10\n   2  spoken 0,3,6 10\n   3  seat FFFFFFFLLL\n   4  spoken 0,3,6 10"
+        ))
+    );
+    assert_eq!(repl.history().len(), 4);
+}
@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The files for a new day's crate, as paths relative to its directory and
+/// their contents. The solver just counts lines until it's filled in, so the
+/// sample tests pass from the start.
+pub fn day_files(day: u32) -> Vec<(PathBuf, String)> {
+    let name = format!("day{}", day);
+    let solution = format!("Day{}", day);
+    let cargo_toml = format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+authors = ["Tony Gallotta <anthony.gallotta@gmail.com>"]
+edition = "2018"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = {{ path = "../aoc-common" }}
+"#,
+        name = name
+    );
+    let main_rs = format!(
+        r#"use aoc_common::run_day;
+use {name}::{solution};
+
+fn main() {{
+    run_day({day}, {solution});
+}}
+"#,
+        name = name,
+        solution = solution,
+        day = day
+    );
+    let lib_rs = format!(
+        r#"//! Day {day}.
+
+use aoc_common::{{read_lines, Result, Solution}};
+use std::io::BufRead;
+
+pub struct {solution};
+
+impl Solution for {solution} {{
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<String>> {{
+        read_lines(input)
+    }}
+
+    fn part1(&self, lines: &Vec<String>) -> Result<usize> {{
+        Ok(lines.len())
+    }}
+
+    fn part2(&self, lines: &Vec<String>, _: &usize) -> Result<usize> {{
+        Ok(lines.len())
+    }}
+}}
+
+#[test]
+fn test_part1() {{
+    let lines = {solution}.parse_file("sample_input.txt").unwrap();
+    assert_eq!({solution}.part1(&lines).unwrap(), 0);
+}}
+
+#[test]
+fn test_part2() {{
+    let lines = {solution}.parse_file("sample_input.txt").unwrap();
+    assert_eq!({solution}.part2(&lines, &0).unwrap(), 0);
+}}
+"#,
+        day = day,
+        solution = solution
+    );
+    vec![
+        (PathBuf::from("Cargo.toml"), cargo_toml),
+        (PathBuf::from("src/main.rs"), main_rs),
+        (PathBuf::from("src/lib.rs"), lib_rs),
+        (PathBuf::from("input.txt"), String::new()),
+        (PathBuf::from("sample_input.txt"), String::new()),
+        (
+            PathBuf::from("answers.txt"),
+            String::from("# <input file> <part> <answer>\n"),
+        ),
+    ]
+}
+
+/// Adds a day to the `members` list of the workspace's `Cargo.toml`, after the
+/// last existing member.
+pub fn add_workspace_member(cargo_toml: &str, day: u32) -> Result<String, String> {
+    let member = format!("\"day{}\"", day);
+    if cargo_toml.contains(&member) {
+        return Ok(String::from(cargo_toml));
+    }
+    let start = cargo_toml
+        .find("members = [")
+        .ok_or("the workspace Cargo.toml has no members list")?;
+    let end = start
+        + cargo_toml[start..]
+            .find(']')
+            .ok_or("the workspace members list isn't closed")?;
+    let mut updated = String::from(&cargo_toml[..end]);
+    updated.push_str(&format!("    {},\n", member));
+    updated.push_str(&cargo_toml[end..]);
+    Ok(updated)
+}
+
+/// Creates `dayN/` under the workspace `root` and adds it to the workspace,
+/// returning the files written. Refuses to touch a day that already exists.
+pub fn create_day(root: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let manifest = root.join("Cargo.toml");
+    let cargo_toml = fs::read_to_string(&manifest)
+        .map_err(|e| format!("Couldn't read {}: {}", manifest.display(), e))?;
+    let cargo_toml = add_workspace_member(&cargo_toml, day)?;
+
+    let mut written = Vec::new();
+    for (file, contents) in day_files(day) {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, contents))
+            .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    fs::write(&manifest, cargo_toml)
+        .map_err(|e| format!("Couldn't write {}: {}", manifest.display(), e))?;
+    Ok(written)
+}
+
+#[test]
+fn test_add_workspace_member() {
+    let cargo_toml = "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n]\n";
+    let updated = add_workspace_member(cargo_toml, 2).unwrap();
+    assert_eq!(
+        updated,
+        "[workspace]\nmembers = [\n    \"aoc\",\n    \"day1\",\n    \"day2\",\n]\n"
+    );
+    assert_eq!(add_workspace_member(&updated, 2).unwrap(), updated);
+    assert!(add_workspace_member("[package]\n", 2).is_err());
+}
+
+#[test]
+fn test_create_day() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n]\n").unwrap();
+
+    let written = create_day(&root, 17).unwrap();
+    assert_eq!(written.len(), 6);
+    let main_rs = fs::read_to_string(root.join("day17/src/main.rs")).unwrap();
+    assert!(main_rs.contains("run_day(17, Day17);"));
+    assert!(fs::read_to_string(root.join("Cargo.toml"))
+        .unwrap()
+        .contains("\"day17\""));
+
+    // A second attempt must leave the existing day alone
+    fs::write(root.join("day17/input.txt"), "my input").unwrap();
+    assert!(create_day(&root, 17).is_err());
+    assert_eq!(
+        fs::read_to_string(root.join("day17/input.txt")).unwrap(),
+        "my input"
+    );
+    fs::remove_dir_all(&root).unwrap();
+}
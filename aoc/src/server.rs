@@ -0,0 +1,344 @@
+use crate::days::{self, DAYS, PARTS};
+use aoc_common::{format_report, info, json_string, CancelToken, Error, Format};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Enough for a request line and a handful of headers; anything longer isn't
+// a client we expect
+const MAX_HEAD: u64 = 8 * 1024;
+
+/// What a single request is allowed to use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Limits {
+    // The largest puzzle input accepted, in bytes
+    pub max_body: usize,
+    // How long a solver may run. Like `run --timeout`, solvers that never
+    // check for cancellation can't be stopped.
+    pub timeout: Duration,
+    // How long to wait for a slow client to send its request
+    pub read_timeout: Duration,
+    // How many connections are handled at once; the rest are turned away
+    pub max_connections: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max_body: 1024 * 1024,
+            timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(5),
+            max_connections: 16,
+        }
+    }
+}
+
+/// An HTTP/1.1 server that solves puzzle inputs posted to
+/// `/day/{n}/part/{p}`, answering with the same JSON as `run --format json`.
+/// Query parameters override the day's `config.txt`, as in
+/// `/day/9/part/1?preamble=5`. It only listens on localhost, and handles each
+/// connection on its own thread, closing it after one response. Connections
+/// beyond `Limits::max_connections` get a 503 straight away.
+pub struct Server {
+    listener: TcpListener,
+    limits: Limits,
+}
+
+impl Server {
+    /// Listens on `127.0.0.1:port`, where port 0 picks a free one.
+    pub fn bind(port: u16, limits: Limits) -> Result<Server, String> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .map_err(|e| format!("Couldn't listen on port {}: {}", port, e))?;
+        Ok(Server { listener, limits })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.listener.local_addr().unwrap()
+    }
+
+    /// Accepts connections until the process is stopped.
+    pub fn serve(&self) {
+        let active = Arc::new(AtomicUsize::new(0));
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if active.fetch_add(1, Ordering::SeqCst) >= self.limits.max_connections {
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        let _ = stream.set_write_timeout(Some(self.limits.read_timeout));
+                        let busy = Response::error(503, "Too many requests in progress");
+                        let _ = write_response(&stream, &busy);
+                        continue;
+                    }
+                    let slot = Slot(Arc::clone(&active));
+                    let limits = self.limits;
+                    thread::spawn(move || {
+                        handle(stream, &limits);
+                        drop(slot);
+                    });
+                }
+                Err(e) => info!("Couldn't accept a connection: {}", e),
+            }
+        }
+    }
+}
+
+// One of the connections being handled, given back when it's dropped, even
+// if the handler panics
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    query: Option<String>,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}\n", json_string(message)),
+        }
+    }
+}
+
+fn handle(stream: TcpStream, limits: &Limits) {
+    let _ = stream.set_read_timeout(Some(limits.read_timeout));
+    let _ = stream.set_write_timeout(Some(limits.read_timeout));
+    let response = match read_request(&mut BufReader::new(&stream), limits.max_body) {
+        Ok(request) => {
+            let response = respond(&request, limits);
+            info!("{} {} {}", request.method, request.path, response.status);
+            response
+        }
+        Err(response) => response,
+    };
+    let _ = write_response(&stream, &response);
+}
+
+fn read_request<R: BufRead>(reader: &mut R, max_body: usize) -> Result<Request, Response> {
+    let mut head = reader.take(MAX_HEAD);
+    let mut line = String::new();
+    read_line(&mut head, &mut line)?;
+    let mut fields = line.split_whitespace();
+    let (method, target) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => {
+            (String::from(method), String::from(target))
+        }
+        _ => return Err(Response::error(400, "Malformed request line")),
+    };
+
+    let mut content_length = None;
+    loop {
+        line.clear();
+        read_line(&mut head, &mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| Response::error(400, "Malformed header"))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, "Malformed Content-Length"))?;
+            content_length = Some(length);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(501, "Chunked requests aren't supported"));
+        }
+    }
+
+    let body = match content_length {
+        Some(length) if length > max_body => {
+            return Err(Response::error(
+                413,
+                &format!("Inputs are limited to {} bytes", max_body),
+            ))
+        }
+        Some(length) => {
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).map_err(|e| read_error(&e))?;
+            body
+        }
+        None if method == "POST" => return Err(Response::error(411, "Content-Length is required")),
+        None => Vec::new(),
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (String::from(path), Some(String::from(query))),
+        None => (target, None),
+    };
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> Result<(), Response> {
+    match reader.read_line(line) {
+        Ok(_) if line.ends_with('\n') => Ok(()),
+        Ok(_) => Err(Response::error(
+            431,
+            "Request head is too long or incomplete",
+        )),
+        Err(e) => Err(read_error(&e)),
+    }
+}
+
+fn read_error(error: &io::Error) -> Response {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Response::error(408, "Timed out reading the request")
+        }
+        io::ErrorKind::UnexpectedEof => Response::error(400, "Request body is incomplete"),
+        _ => Response::error(400, &error.to_string()),
+    }
+}
+
+fn respond(request: &Request, limits: &Limits) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let (day, part) = match segments.as_slice() {
+        ["day", day, "part", part] => (day.parse::<u32>(), part.parse::<u32>()),
+        _ => return Response::error(404, "Expected /day/{n}/part/{p}"),
+    };
+    let day = match day {
+        Ok(day) if DAYS.contains(&day) => day,
+        _ => return Response::error(404, "No such day"),
+    };
+    let part = match part {
+        Ok(part) if PARTS.contains(&part) => part,
+        _ => return Response::error(404, "No such part"),
+    };
+    if request.method != "POST" {
+        return Response::error(405, "Post the puzzle input");
+    }
+    let input = match std::str::from_utf8(&request.body) {
+        Ok(input) => input,
+        Err(_) => return Response::error(400, "The input isn't UTF-8"),
+    };
+    let overrides: Vec<String> = request
+        .query
+        .iter()
+        .flat_map(|q| q.split('&'))
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+    let solver = match days::solver(day, &overrides) {
+        Ok(solver) => solver,
+        Err(message) => return Response::error(400, &message),
+    };
+
+    let token = CancelToken::with_timeout(limits.timeout);
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        token.install(|| solver.solve_str(input, &[part]))
+    }));
+    match solved {
+        Ok(Ok(report)) => Response {
+            status: 200,
+            body: format_report(day, &report, Format::Json),
+        },
+        Ok(Err(e @ Error::Cancelled { .. })) => Response::error(504, &e.to_string()),
+        Ok(Err(e)) => Response::error(422, &e.to_string()),
+        Err(_) => Response::error(500, "The solver panicked"),
+    }
+}
+
+fn write_response<W: Write>(mut writer: W, response: &Response) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        reason(response.status),
+        response.body.len()
+    );
+    if response.status == 405 {
+        head.push_str("Allow: POST\r\n");
+    }
+    head.push_str("\r\n");
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(response.body.as_bytes())?;
+    writer.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+fn parse(request: &str) -> Result<Request, Response> {
+    read_request(&mut request.as_bytes(), 16)
+}
+
+#[test]
+fn test_read_request() {
+    let request =
+        parse("POST /day/1/part/2?target=10 HTTP/1.1\r\ncontent-length: 4\r\n\r\n1\n9\nextra")
+            .unwrap();
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/day/1/part/2");
+    assert_eq!(request.query.as_deref(), Some("target=10"));
+    assert_eq!(request.body, b"1\n9\n");
+
+    let request = parse("GET / HTTP/1.0\n\n").unwrap();
+    assert!(request.body.is_empty());
+
+    let status = |request: &str| parse(request).unwrap_err().status;
+    assert_eq!(status("POST /day/1/part/1\r\n\r\n"), 400);
+    assert_eq!(status("POST / HTTP/1.1\r\nno colon\r\n\r\n"), 400);
+    assert_eq!(status("POST / HTTP/1.1\r\n\r\n"), 411);
+    assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 17\r\n\r\n"), 413);
+    assert_eq!(
+        status("POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\n1\n"),
+        400
+    );
+    assert_eq!(
+        status("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n"),
+        501
+    );
+    assert_eq!(status("POST / HTTP/1.1\r\nContent-Length: 5"), 431);
+}
+
+#[test]
+fn test_write_response() {
+    let mut written = Vec::new();
+    write_response(&mut written, &Response::error(405, "no")).unwrap();
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        "HTTP/1.1 405 Method Not Allowed\r\nContent-Type: application/json\r\n\
+         Content-Length: 15\r\nConnection: close\r\nAllow: POST\r\n\r\n{\"error\":\"no\"}\n"
+    );
+}
@@ -0,0 +1,206 @@
+use crate::answers::{self, KnownAnswer};
+use aoc_common::Report;
+use std::fmt;
+use std::time::Duration;
+
+/// How a part's answer compares with the one recorded in `answers.txt`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    // No answer is recorded for this part
+    Unchecked,
+    // The day failed or panicked before producing an answer
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            Status::Unchecked => write!(f, "unchecked"),
+            Status::Error(message) => write!(f, "ERROR: {}", message),
+        }
+    }
+}
+
+/// One line of the summary table. A day that failed gets a single row with
+/// no part.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Row {
+    pub day: u32,
+    pub part: Option<u32>,
+    pub answer: String,
+    pub time: Option<Duration>,
+    pub status: Status,
+}
+
+/// The rows for one day's run over its `input.txt`.
+pub fn day_rows(
+    day: u32,
+    outcome: &Result<Report, String>,
+    known_answers: &[KnownAnswer],
+) -> Vec<Row> {
+    let report = match outcome {
+        Ok(report) => report,
+        Err(message) => {
+            return vec![Row {
+                day,
+                part: None,
+                answer: String::new(),
+                time: None,
+                status: Status::Error(message.clone()),
+            }]
+        }
+    };
+    report
+        .answers
+        .iter()
+        .map(|answer| {
+            let status = match answers::expected(known_answers, "input.txt", answer.part) {
+                Some(expected) if expected == answer.answer => Status::Pass,
+                Some(expected) => Status::Fail {
+                    expected: String::from(expected),
+                },
+                None => Status::Unchecked,
+            };
+            Row {
+                day,
+                part: Some(answer.part),
+                answer: answer.answer.clone(),
+                time: Some(answer.elapsed),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Lays the rows out as a table, followed by a line counting the results.
+pub fn format_table(rows: &[Row], wall_time: Duration) -> String {
+    let cells: Vec<[String; 5]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.day.to_string(),
+                row.part
+                    .map_or_else(|| String::from("-"), |p| p.to_string()),
+                row.answer.clone(),
+                row.time.map_or_else(String::new, millis),
+                row.status.to_string(),
+            ]
+        })
+        .collect();
+    let headers = ["Day", "Part", "Answer", "Time", "Result"];
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let line = |cells: &[&str]| {
+        let mut line = String::new();
+        for (i, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+            // The last column is left ragged; the rest are padded, with
+            // numbers right-aligned
+            match i {
+                4 => line.push_str(cell),
+                2 => line.push_str(&format!("{:<1$}  ", cell, width)),
+                _ => line.push_str(&format!("{:>1$}  ", cell, width)),
+            }
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut table = line(&headers);
+    for row in &cells {
+        table.push_str(&line(
+            &row.iter().map(String::as_str).collect::<Vec<&str>>(),
+        ));
+    }
+    let count = |f: fn(&Status) -> bool| rows.iter().filter(|r| f(&r.status)).count();
+    table.push_str(&format!(
+        "{} passed, {} failed, {} unchecked, {} errors in {}\n",
+        count(|s| *s == Status::Pass),
+        count(|s| matches!(s, Status::Fail { .. })),
+        count(|s| *s == Status::Unchecked),
+        count(|s| matches!(s, Status::Error(_))),
+        millis(wall_time)
+    ));
+    table
+}
+
+/// Whether every row's answer matched, counting unchecked ones as fine.
+pub fn all_passed(rows: &[Row]) -> bool {
+    rows.iter()
+        .all(|r| matches!(r.status, Status::Pass | Status::Unchecked))
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[test]
+fn test_day_rows() {
+    use aoc_common::PartAnswer;
+    let known_answers = answers::parse_manifest("input.txt 1 7\ninput.txt 2 8\n").unwrap();
+    let report = Report {
+        parse_time: Duration::from_millis(1),
+        parse_memory: None,
+        answers: vec![
+            PartAnswer {
+                part: 1,
+                answer: String::from("7"),
+                elapsed: Duration::from_millis(2),
+                memory: None,
+            },
+            PartAnswer {
+                part: 2,
+                answer: String::from("9"),
+                elapsed: Duration::from_millis(3),
+                memory: None,
+            },
+        ],
+    };
+    let rows = day_rows(4, &Ok(report), &known_answers);
+    assert_eq!(rows[0].status, Status::Pass);
+    assert_eq!(
+        rows[1].status,
+        Status::Fail {
+            expected: String::from("8")
+        }
+    );
+    assert!(!all_passed(&rows));
+
+    let rows = day_rows(4, &Err(String::from("panicked: oops")), &known_answers);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].status.to_string(), "ERROR: panicked: oops");
+}
+
+#[test]
+fn test_format_table() {
+    let rows = vec![
+        Row {
+            day: 1,
+            part: Some(1),
+            answer: String::from("1000"),
+            time: Some(Duration::from_micros(1500)),
+            status: Status::Pass,
+        },
+        Row {
+            day: 12,
+            part: None,
+            answer: String::new(),
+            time: None,
+            status: Status::Error(String::from("input.txt:1:1: expected a number")),
+        },
+    ];
+    assert_eq!(
+        format_table(&rows, Duration::from_millis(20)),
+        "Day  Part  Answer     Time  Result\n\
+         \x20 1     1  1000    1.500ms  pass\n\
+         \x2012     -                   ERROR: input.txt:1:1: expected a number\n\
+         1 passed, 0 failed, 0 unchecked, 1 errors in 20.000ms\n"
+    );
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+/// When each watched file in a day's directory was last modified.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Records the modification times of the files that affect a day's answers:
+/// the sources under `src/`, and the `Cargo.toml` and `.txt` files (inputs,
+/// config and answers) at the top of the day's directory. Files that can't
+/// be read are left out, so they show up as changed once they can be.
+pub fn snapshot(dir: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    for path in list_dir(dir) {
+        let watched = path.extension().is_some_and(|e| e == "txt")
+            || path.file_name().is_some_and(|n| n == "Cargo.toml");
+        if watched {
+            add_file(&mut files, path);
+        }
+    }
+    let mut dirs = vec![dir.join("src")];
+    while let Some(dir) = dirs.pop() {
+        for path in list_dir(&dir) {
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                add_file(&mut files, path);
+            }
+        }
+    }
+    files
+}
+
+fn list_dir(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
+
+fn add_file(files: &mut Snapshot, path: PathBuf) {
+    if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+        files.insert(path, modified);
+    }
+}
+
+/// The files that were added, removed or modified between two snapshots.
+pub fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    paths.extend(before.keys().filter(|p| !after.contains_key(*p)).cloned());
+    paths.sort();
+    paths
+}
+
+/// Picks the answers out of a day binary's text output.
+pub fn parse_answers(output: &str) -> Vec<(u32, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (part, answer) = line.strip_prefix("PART ")?.split_once(": ")?;
+            Some((part.parse().ok()?, String::from(answer)))
+        })
+        .collect()
+}
+
+/// Describes each part's answer compared with the previous run's, if there
+/// was one.
+pub fn diff_answers(previous: Option<&[(u32, String)]>, current: &[(u32, String)]) -> Vec<String> {
+    let previous = match previous {
+        Some(previous) => previous,
+        None => {
+            return current
+                .iter()
+                .map(|(part, answer)| format!("Part {}: {}", part, answer))
+                .collect()
+        }
+    };
+    let find = |answers: &[(u32, String)], part: u32| {
+        answers
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, a)| a.clone())
+    };
+    let mut parts: Vec<u32> = previous.iter().chain(current).map(|(p, _)| *p).collect();
+    parts.sort_unstable();
+    parts.dedup();
+    parts
+        .into_iter()
+        .map(|part| match (find(previous, part), find(current, part)) {
+            (Some(before), Some(after)) if before == after => {
+                format!("Part {}: {} (unchanged)", part, after)
+            }
+            (Some(before), Some(after)) => format!("Part {}: {} -> {}", part, before, after),
+            (None, Some(after)) => format!("Part {}: {} (new)", part, after),
+            (Some(before), None) => format!("Part {}: {} -> no answer", part, before),
+            (None, None) => unreachable!(),
+        })
+        .collect()
+}
+
+// Runs cargo in the day's directory, so the day binary finds its input.txt
+// and config.txt there. Compiler errors and test output go straight through.
+fn cargo(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(dir).args(args);
+    command
+}
+
+/// Builds the day's crate.
+pub fn build(dir: &Path, day: u32) -> Result<(), String> {
+    let package = format!("day{}", day);
+    let status = cargo(dir, &["build", "-q", "-p", &package])
+        .status()
+        .map_err(|e| format!("Couldn't run cargo: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} failed to build", package))
+    }
+}
+
+/// Runs the day's binary on its `input.txt` and returns its answers.
+pub fn solve(dir: &Path, day: u32) -> Result<Vec<(u32, String)>, String> {
+    let package = format!("day{}", day);
+    let output = cargo(dir, &["run", "-q", "-p", &package, "--", "-q"])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Couldn't run cargo: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("{} failed on input.txt", package));
+    }
+    Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Runs the day's tests, which check its sample inputs.
+pub fn test(dir: &Path, day: u32) -> Result<(), String> {
+    let package = format!("day{}", day);
+    let status = cargo(dir, &["test", "-q", "-p", &package])
+        .status()
+        .map_err(|e| format!("Couldn't run cargo: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} sample tests failed", package))
+    }
+}
+
+#[test]
+fn test_snapshot_and_changed() {
+    use std::time::Duration;
+    let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    fs::create_dir_all(dir.join("src/bin")).unwrap();
+    for file in ["input.txt", "notes.md", "src/lib.rs", "src/bin/extra.rs"] {
+        fs::write(dir.join(file), "").unwrap();
+    }
+    let before = snapshot(&dir);
+    assert_eq!(before.len(), 3);
+    assert!(changed(&before, &snapshot(&dir)).is_empty());
+
+    let later = SystemTime::now() + Duration::from_secs(5);
+    let lib = fs::File::options()
+        .write(true)
+        .open(dir.join("src/lib.rs"))
+        .unwrap();
+    lib.set_modified(later).unwrap();
+    fs::write(dir.join("sample_input_2.txt"), "").unwrap();
+    fs::remove_file(dir.join("input.txt")).unwrap();
+    assert_eq!(
+        changed(&before, &snapshot(&dir)),
+        vec![
+            dir.join("input.txt"),
+            dir.join("sample_input_2.txt"),
+            dir.join("src/lib.rs")
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_parse_answers() {
+    let output = "PART 1: 42\nPART 2: a b: c\nExecution completed in 3ms\n";
+    assert_eq!(
+        parse_answers(output),
+        vec![(1, String::from("42")), (2, String::from("a b: c"))]
+    );
+}
+
+#[test]
+fn test_diff_answers() {
+    let answers = |pairs: &[(u32, &str)]| -> Vec<(u32, String)> {
+        pairs.iter().map(|(p, a)| (*p, String::from(*a))).collect()
+    };
+    let previous = answers(&[(1, "7"), (2, "8")]);
+    assert_eq!(
+        diff_answers(None, &previous),
+        vec!["Part 1: 7", "Part 2: 8"]
+    );
+    assert_eq!(
+        diff_answers(Some(&previous), &answers(&[(1, "7"), (2, "9")])),
+        vec!["Part 1: 7 (unchanged)", "Part 2: 8 -> 9"]
+    );
+    assert_eq!(
+        diff_answers(Some(&answers(&[(1, "7")])), &answers(&[(2, "9")])),
+        vec!["Part 1: 7 -> no answer", "Part 2: 9 (new)"]
+    );
+}
@@ -0,0 +1,28 @@
+// Installs the counting allocator, as `--features count-allocations` does for
+// the runner, and checks that the reports measure each phase. This is the
+// only test in the binary so no other test's allocations get counted.
+use aoc::days;
+use aoc_common::alloc::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn reports_memory_per_phase() {
+    let overrides = [
+        String::from("part1_turns=10"),
+        String::from("part2_turns=100000"),
+    ];
+    let solver = days::solver(15, &overrides).unwrap();
+    let input = days::day_dir(15).join("sample_input_1.txt");
+    let report = solver.solve(&input.to_string_lossy(), &[1, 2]).unwrap();
+
+    let parse = report.parse_memory.unwrap();
+    assert!(parse.allocations > 0);
+    let part1 = report.answers[0].memory.unwrap();
+    let part2 = report.answers[1].memory.unwrap();
+    // Part 2 remembers thousands of numbers where part 1 remembers a handful
+    assert!(part2.peak_bytes > 10 * part1.peak_bytes.max(1024));
+    assert!(part2.allocated_bytes >= part2.peak_bytes);
+    assert!(format!("{}", report).contains("Memory for part 2: "));
+}
@@ -0,0 +1,99 @@
+// Runs every day against its real input and sample inputs, comparing the
+// results with the answers recorded in each day's answers.txt.
+use aoc::answers::check_day;
+use aoc::fingerprint;
+
+fn check(day: u32) {
+    // A changed input makes the recorded answers meaningless
+    for warning in fingerprint::check_day(day) {
+        eprintln!("warning: {}", warning);
+    }
+    let mismatches = check_day(day).unwrap();
+    for m in &mismatches {
+        eprintln!(
+            "Day {} {} {:?} part {}: expected {}, got {}",
+            m.day, m.input, m.params, m.part, m.expected, m.actual
+        );
+    }
+    assert!(mismatches.is_empty());
+}
+
+#[test]
+fn day1() {
+    check(1);
+}
+
+#[test]
+fn day2() {
+    check(2);
+}
+
+#[test]
+fn day3() {
+    check(3);
+}
+
+#[test]
+fn day4() {
+    check(4);
+}
+
+#[test]
+fn day5() {
+    check(5);
+}
+
+#[test]
+fn day6() {
+    check(6);
+}
+
+#[test]
+fn day7() {
+    check(7);
+}
+
+#[test]
+fn day8() {
+    check(8);
+}
+
+#[test]
+fn day9() {
+    check(9);
+}
+
+#[test]
+fn day10() {
+    check(10);
+}
+
+#[test]
+fn day11() {
+    check(11);
+}
+
+#[test]
+fn day12() {
+    check(12);
+}
+
+#[test]
+fn day13() {
+    check(13);
+}
+
+#[test]
+fn day14() {
+    check(14);
+}
+
+#[test]
+fn day15() {
+    check(15);
+}
+
+#[test]
+fn day16() {
+    check(16);
+}
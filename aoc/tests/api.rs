@@ -0,0 +1,56 @@
+// Calls the days' solvers directly through their public functions, the way
+// other tools would, rather than through the Solution trait.
+use aoc_common::Solution;
+
+#[test]
+fn seat() {
+    let seat = day5::get_seat("BFFFBBFRRR").unwrap();
+    assert_eq!((seat.row, seat.column, seat.id), (70, 7, 567));
+}
+
+#[test]
+fn bags() {
+    let bags = day7::to_bags(&[
+        String::from("shiny gold bags contain 2 dark red bags."),
+        String::from("dark red bags contain 3 dark blue bags."),
+        String::from("dark blue bags contain no other bags."),
+    ])
+    .unwrap();
+    let shiny_gold = day7::get_bag("shiny gold", &bags).unwrap();
+    assert_eq!(shiny_gold.allowed_bags["dark red"], 2);
+    assert_eq!(day7::contained_bag_count(shiny_gold, &bags).unwrap(), 8);
+    assert_eq!(day7::count_possible_holders("dark blue", &bags).unwrap(), 2);
+}
+
+#[test]
+fn program() {
+    let program = day8::Day8.parse_str("nop +0\nacc +1\njmp -2").unwrap();
+    assert_eq!(program[2].op, day8::Op::Jmp);
+    assert_eq!(day8::run_program(&program).unwrap(), (1, false));
+    let fixed = day8::copy_with_line_change(&program, 2);
+    assert_eq!(day8::run_program(&fixed).unwrap(), (1, true));
+}
+
+#[test]
+fn navigation() {
+    use day12::{Action, Direction};
+    let instructions = day12::to_instructions(&[String::from("R270"), String::from("F5")]).unwrap();
+    assert_eq!(instructions[0].action, Action::R);
+    assert_eq!(Direction::E.rotate(270), Direction::N);
+    assert_eq!(day12::run_part1(&instructions), 5);
+}
+
+#[test]
+fn ticket_fields() {
+    let input = day16::Day16::default()
+        .parse_str(
+            "class: 0-1 or 4-19\nrow: 0-5 or 8-19\nseat: 0-13 or 16-19\n\n\
+             your ticket:\n11,12,13\n\nnearby tickets:\n3,9,18\n15,1,5\n5,14,9",
+        )
+        .unwrap();
+    assert_eq!(input.rules[0].field_name, "class");
+    assert_eq!(
+        day16::get_ordered_fields(&input).unwrap(),
+        ["row", "class", "seat"]
+    );
+}
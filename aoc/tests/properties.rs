@@ -0,0 +1,105 @@
+// Solves generated inputs whose answers were planted when they were built,
+// checking each day's solver against them over many seeds. A failure names
+// its seed; `aoc generate --day <N> --seed <S>` writes out that input.
+use aoc::days;
+use aoc::generate::generate;
+use aoc_common::Rng;
+
+// Days whose parsing builds regexes, or whose games run for thousands of
+// turns, get fewer cases to keep debug builds quick
+fn check(day: u32, cases: u64) {
+    for seed in 0..cases {
+        let generated = generate(day, &mut Rng::new(seed)).unwrap();
+        let solver = days::solver(day, &generated.overrides).unwrap();
+        let report = solver
+            .solve_str(&generated.input, &generated.parts())
+            .unwrap_or_else(|e| panic!("Day {} seed {}: {}", day, seed, e));
+        for (answer, (part, expected)) in report.answers.iter().zip(&generated.answers) {
+            assert_eq!(
+                &answer.answer, expected,
+                "Day {} part {} with seed {}",
+                day, part, seed
+            );
+        }
+    }
+}
+
+#[test]
+fn day1() {
+    check(1, 1000);
+}
+
+#[test]
+fn day2() {
+    check(2, 1000);
+}
+
+#[test]
+fn day3() {
+    check(3, 1000);
+}
+
+#[test]
+fn day4() {
+    check(4, 1000);
+}
+
+#[test]
+fn day5() {
+    check(5, 1000);
+}
+
+#[test]
+fn day6() {
+    check(6, 1000);
+}
+
+#[test]
+fn day7() {
+    check(7, 200);
+}
+
+#[test]
+fn day8() {
+    check(8, 1000);
+}
+
+#[test]
+fn day9() {
+    check(9, 1000);
+}
+
+#[test]
+fn day10() {
+    check(10, 1000);
+}
+
+#[test]
+fn day11() {
+    check(11, 1000);
+}
+
+#[test]
+fn day12() {
+    check(12, 500);
+}
+
+#[test]
+fn day13() {
+    check(13, 1000);
+}
+
+#[test]
+fn day14() {
+    check(14, 500);
+}
+
+#[test]
+fn day15() {
+    check(15, 300);
+}
+
+#[test]
+fn day16() {
+    check(16, 500);
+}
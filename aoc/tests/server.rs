@@ -0,0 +1,132 @@
+// Starts the HTTP server on a free loopback port and talks to it over a
+// socket, the way other tools would.
+use aoc::server::{Limits, Server};
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+fn start(limits: Limits) -> SocketAddr {
+    let server = Server::bind(0, limits).unwrap();
+    let addr = server.local_addr();
+    thread::spawn(move || server.serve());
+    addr
+}
+
+// Sends a raw request and returns the status code and body of the response
+fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, String::from(body))
+}
+
+fn post(addr: SocketAddr, path: &str, input: &str) -> (u16, String) {
+    send(
+        addr,
+        &format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            input.len(),
+            input
+        ),
+    )
+}
+
+const DAY1_SAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+#[test]
+fn solves_posted_input() {
+    let addr = start(Limits::default());
+    assert!(addr.ip().is_loopback());
+
+    let (status, body) = post(addr, "/day/1/part/1", DAY1_SAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.starts_with("{\"day\":1,\"part\":1,\"answer\":\"514579\",\"parse_ms\":"));
+    assert!(body.contains("\"solve_ms\":"));
+
+    let (status, body) = post(addr, "/day/1/part/2", DAY1_SAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.contains("\"answer\":\"241861950\""));
+
+    let sample = "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+    let (status, body) = post(addr, "/day/9/part/1?preamble=5", sample);
+    assert_eq!(status, 200);
+    assert!(body.contains("\"answer\":\"127\""));
+}
+
+#[test]
+fn rejects_bad_requests() {
+    let addr = start(Limits {
+        max_body: 64,
+        ..Limits::default()
+    });
+    assert_eq!(post(addr, "/day/1", DAY1_SAMPLE).0, 404);
+    assert_eq!(post(addr, "/day/30/part/1", DAY1_SAMPLE).0, 404);
+    assert_eq!(post(addr, "/day/1/part/3", DAY1_SAMPLE).0, 404);
+    assert_eq!(send(addr, "GET /day/1/part/1 HTTP/1.1\r\n\r\n").0, 405);
+    assert_eq!(send(addr, "POST /day/1/part/1 HTTP/1.1\r\n\r\n").0, 411);
+    assert_eq!(post(addr, "/day/1/part/1", &"1\n".repeat(40)).0, 413);
+    assert_eq!(post(addr, "/day/1/part/1?bogus=1", DAY1_SAMPLE).0, 400);
+
+    let (status, body) = post(addr, "/day/1/part/1", "12\nabc\n");
+    assert_eq!(status, 422);
+    assert!(body.contains("2:1: expected a number"));
+}
+
+#[test]
+fn survives_cyclic_bag_rules() {
+    let addr = start(Limits::default());
+    let rules = "shiny gold bags contain 1 dark red bag.\n\
+                 dark red bags contain 1 shiny gold bag.\n";
+    let (status, body) = post(addr, "/day/7/part/2", rules);
+    assert_eq!(status, 422);
+    assert!(body.contains("bag rules contain a cycle through"));
+
+    let (status, body) = post(addr, "/day/1/part/1", DAY1_SAMPLE);
+    assert_eq!(status, 200);
+    assert!(body.contains("\"answer\":\"514579\""));
+}
+
+#[test]
+fn stops_slow_solvers_and_clients() {
+    let addr = start(Limits {
+        timeout: Duration::from_millis(0),
+        read_timeout: Duration::from_millis(200),
+        ..Limits::default()
+    });
+    let (status, body) = post(addr, "/day/13/part/2", "939\n7,13,x,x,59,x,31,19\n");
+    assert_eq!(status, 504);
+    assert!(body.contains("part 2 timed out"));
+    // Far too many turns to finish, so only the timeout ends it
+    let (status, _) = post(addr, "/day/15/part/2?part2_turns=1000000000000", "0,3,6\n");
+    assert_eq!(status, 504);
+
+    // Promises a body that never comes
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .write_all(b"POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 10\r\n\r\n12")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 408 "));
+    stream.shutdown(Shutdown::Both).unwrap();
+}
+
+#[test]
+fn turns_away_extra_connections() {
+    let addr = start(Limits {
+        max_connections: 1,
+        ..Limits::default()
+    });
+    // Takes the only slot without sending a request
+    let idle = TcpStream::connect(addr).unwrap();
+    let mut turned_away = TcpStream::connect(addr).unwrap();
+    let mut response = String::new();
+    turned_away.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 503 "));
+    drop(idle);
+}
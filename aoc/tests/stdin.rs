@@ -0,0 +1,41 @@
+// Pipes a sample input into the runner to check that `--input -` reads stdin.
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+#[test]
+fn reads_input_from_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "--day", "1", "--input", "-", "--format", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"1721\n979\n366\n299\n675\n1456\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"part\":1,\"answer\":\"514579\""));
+    assert!(stdout.contains("\"part\":2,\"answer\":\"241861950\""));
+}
+
+#[test]
+fn reports_errors_on_stdin_by_name() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "--day", "1", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"12\nabc\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("<stdin>:2:1: expected a number, found \"abc\""));
+}
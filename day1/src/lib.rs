@@ -0,0 +1,755 @@
+//! Day 1: Report Repair. Finds the expenses that add up to a target sum.
+
+use aoc_common::{debug, read_numbers, warn, Config, Error, Result, Solution};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// The integer types expenses can be read as. Signed types allow refunds,
+/// written as negative expenses, and `u128` very large ledgers. Arithmetic on
+/// them is checked: products that don't fit are an `Error::Overflow`, and
+/// sums that overflow while searching are simply too big or too small.
+pub trait Expense: Copy + Ord + Hash + Debug + Display + FromStr + From<u16> + TryFrom<usize>
+                   + TryInto<u64> {
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_expense {
+    ($($type:ty),*) => {$(
+        impl Expense for $type {
+            fn checked_add(self, other: $type) -> Option<$type> {
+                <$type>::checked_add(self, other)
+            }
+            fn checked_sub(self, other: $type) -> Option<$type> {
+                <$type>::checked_sub(self, other)
+            }
+            fn checked_mul(self, other: $type) -> Option<$type> {
+                <$type>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_expense!(i32, i64, i128, u32, u64, u128);
+
+pub struct Day1<T: Expense = i64> {
+    // The sum the expenses have to add up to
+    pub target: T,
+}
+
+impl<T: Expense> Default for Day1<T> {
+    fn default() -> Day1<T> {
+        Day1 { target: T::from(2020) }
+    }
+}
+
+impl<T: Expense> Solution for Day1<T> {
+    type Input = Vec<T>;
+    type Part1 = T;
+    type Part2 = T;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<T>> {
+        read_numbers(input)
+    }
+
+    fn part1(&self, expenses: &Vec<T>) -> Result<T> {
+        let (e1, e2) = summing_pair(expenses, self.target)?;
+        product(&[e1, e2])
+    }
+
+    fn part2(&self, expenses: &Vec<T>, _: &T) -> Result<T> {
+        let (e1, e2, e3) = summing_triplet(expenses, self.target)?;
+        product(&[e1, e2, e3])
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.target = config.get("target", self.target, "a number")?;
+        Ok(())
+    }
+}
+
+/// Multiplies the expenses together, failing if the product doesn't fit.
+pub fn product<T: Expense>(expenses: &[T]) -> Result<T> {
+    expenses.iter().try_fold(T::from(1), |product, e| {
+        product.checked_mul(*e).ok_or_else(|| {
+            let factors: Vec<String> = expenses.iter().map(T::to_string).collect();
+            Error::Overflow(format!("{} doesn't fit", factors.join(" * ")))
+        })
+    })
+}
+
+/// Finds two different expenses that add up to `target`.
+pub fn summing_pair<T: Expense>(expenses: &[T], target: T) -> Result<(T, T)> {
+    // Where each value was last seen, so an expense isn't paired with itself
+    let expense_indices :HashMap<T, usize> = expenses.iter()
+        .enumerate()
+        .map(|(i, e)| (*e, i))
+        .collect();
+    for (i, e1) in expenses.iter().enumerate() {
+        // A difference that doesn't fit in T can't be one of the expenses
+        if let Some(e2) = target.checked_sub(*e1) {
+            match expense_indices.get(&e2) {
+                Some(j) if *j != i => return Ok((*e1, e2)),
+                _ => {}
+            }
+        }
+    }
+    Err(Error::NoAnswer(format!("no two expenses sum to {}", target)))
+}
+
+
+/// Two expenses on different lines that add up to the target.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MatchedPair<T: Expense = i64> {
+    // Line numbers from 1, with the earlier line first
+    pub first_line: usize,
+    pub first: T,
+    pub second_line: usize,
+    pub second: T,
+}
+
+/// Reads expenses a line at a time, calling `found` with every pair adding up
+/// to `target` as soon as the second of them is read, so a log can be watched
+/// as it grows. Lines that aren't numbers are skipped with a warning rather
+/// than ending the stream. Returns how many expenses were read.
+pub fn stream_pairs<T: Expense, R: BufRead>(input: R, target: T,
+                                            mut found: impl FnMut(&MatchedPair<T>))
+                                            -> Result<usize> {
+    // The lines each value has been seen on so far
+    let mut seen: HashMap<T, Vec<usize>> = HashMap::new();
+    let mut count = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line = line.map_err(|source| Error::Io { file: None, source })?;
+        let expense: T = match line.parse() {
+            Ok(expense) => expense,
+            Err(_) => {
+                warn!("skipping line {}: {:?} isn't a number", i + 1, line);
+                continue;
+            }
+        };
+        if let Some(first) = target.checked_sub(expense) {
+            for first_line in seen.get(&first).into_iter().flatten() {
+                found(&MatchedPair {
+                    first_line: *first_line,
+                    first,
+                    second_line: i + 1,
+                    second: expense,
+                });
+            }
+        }
+        seen.entry(expense).or_default().push(i + 1);
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Finds three different expenses that add up to `target`, in the order they
+/// appear. Sorts the expenses, then for each one closes in on the other two
+/// from both ends of the ones after it: O(n²).
+pub fn summing_triplet<T: Expense>(expenses: &[T], target: T) -> Result<(T, T, T)> {
+    let mut sorted: Vec<(T, usize)> = expenses.iter()
+        .enumerate()
+        .map(|(i, e)| (*e, i))
+        .collect();
+    sorted.sort_unstable();
+    let zero = T::from(0);
+    for (n, (e1, i)) in sorted.iter().enumerate() {
+        // The expenses after it are at least as big, so can only add to it
+        if *e1 > target && *e1 >= zero {
+            break;
+        }
+        let (mut low, mut high) = (n + 1, sorted.len());
+        while low + 1 < high {
+            let (e2, e3) = (sorted[low].0, sorted[high - 1].0);
+            // With e1 <= e2 <= e3, adding the outer two first means a sum only
+            // overflows when the whole total is out of T's range
+            let ordering = match e1.checked_add(e3) {
+                Some(outer) => compare_sum(outer, e2, target),
+                None => compare_sum(*e1, e3, target),
+            };
+            match ordering {
+                Ordering::Less => low += 1,
+                Ordering::Greater => high -= 1,
+                Ordering::Equal => {
+                    let mut indices = [*i, sorted[low].1, sorted[high - 1].1];
+                    indices.sort_unstable();
+                    let [e1, e2, e3] = indices.map(|i| expenses[i]);
+                    debug!("{} + {} + {} = {}", e1, e2, e3, target);
+                    return Ok((e1, e2, e3));
+                }
+            }
+        }
+    }
+    Err(Error::NoAnswer(format!("no three expenses sum to {}", target)))
+}
+
+// Compares `a + b` with `target`. A sum that overflows is past the end of T's
+// range on the side of its terms, which have the same sign, so it's further
+// that way than any target.
+fn compare_sum<T: Expense>(a: T, b: T, target: T) -> Ordering {
+    match a.checked_add(b) {
+        Some(sum) => sum.cmp(&target),
+        None if b >= T::from(0) => Ordering::Greater,
+        None => Ordering::Less,
+    }
+}
+
+/// How `k_sum_with` searches for combinations.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    // Sorts the values, fixes all but two of them in turn, and closes in on
+    // the last two from both ends: O(n^(k-1)) time
+    TwoPointer,
+    // Splits each combination into its lower and upper indices, and matches
+    // up the sums of every half: O(n^(k/2)) time and memory
+    MeetInTheMiddle,
+}
+
+/// Finds every combination of `k` different entries adding up to `target`,
+/// each as its indices in increasing order, with the combinations sorted.
+/// Repeated values give one combination per choice of index. Uses two
+/// pointers up to k = 3 and meets in the middle beyond that. Fails if there
+/// are fewer than `k` entries, or, when some values are negative, if a
+/// partial sum it needs doesn't fit in `T`.
+pub fn k_sum<T: Expense>(values: &[T], k: usize, target: T) -> Result<Vec<Vec<usize>>> {
+    let strategy = if k <= 3 { Strategy::TwoPointer } else { Strategy::MeetInTheMiddle };
+    k_sum_with(values, k, target, strategy)
+}
+
+pub fn k_sum_with<T: Expense>(values: &[T], k: usize, target: T, strategy: Strategy)
+                              -> Result<Vec<Vec<usize>>> {
+    if k > values.len() {
+        return Err(Error::NoAnswer(format!("can't choose {} of {} values", k, values.len())));
+    }
+    let mut found = Vec::new();
+    match strategy {
+        Strategy::TwoPointer => {
+            let mut sorted: Vec<(T, usize)> = values.iter()
+                .enumerate()
+                .map(|(i, value)| (*value, i))
+                .collect();
+            sorted.sort_unstable();
+            two_pointer(&sorted, k, target, &mut Vec::new(), &mut found)?;
+        }
+        Strategy::MeetInTheMiddle => meet_in_the_middle(values, k, target, &mut found)?,
+    }
+    for combination in &mut found {
+        combination.sort_unstable();
+    }
+    found.sort_unstable();
+    debug!("{} combinations of {} sum to {}", found.len(), k, target);
+    Ok(found)
+}
+
+fn overflow<T: Expense>(a: T, operator: char, b: T) -> Error {
+    Error::Overflow(format!("{} {} {} doesn't fit", a, operator, b))
+}
+
+// Whether k copies of `value`, which isn't negative, add up to more than
+// `target`
+fn exceeds<T: Expense>(value: T, k: usize, target: T) -> bool {
+    if value == T::from(0) {
+        return value > target;
+    }
+    match T::try_from(k) {
+        Ok(k) => value.checked_mul(k).is_none_or(|total| total > target),
+        // More copies than T can count, of a value of at least 1
+        Err(_) => true,
+    }
+}
+
+// Fixes the smallest remaining value and looks for the other k - 1 among the
+// values after it
+fn two_pointer<T: Expense>(sorted: &[(T, usize)], k: usize, target: T, chosen: &mut Vec<usize>,
+                           found: &mut Vec<Vec<usize>>) -> Result<()> {
+    let with = |chosen: &Vec<usize>, indices: &[usize]| {
+        let mut combination = chosen.clone();
+        combination.extend_from_slice(indices);
+        combination
+    };
+    match k {
+        0 if target == T::from(0) => found.push(chosen.clone()),
+        0 => {}
+        1 => {
+            for (value, i) in sorted {
+                if *value == target {
+                    found.push(with(chosen, &[*i]));
+                }
+            }
+        }
+        2 => {
+            let (mut low, mut high) = (0, sorted.len());
+            while low + 1 < high {
+                let (low_value, high_value) = (sorted[low].0, sorted[high - 1].0);
+                match compare_sum(low_value, high_value, target) {
+                    Ordering::Less => low += 1,
+                    Ordering::Greater => high -= 1,
+                    Ordering::Equal if low_value == high_value => {
+                        // Everything in between is the same value, so any two will do
+                        for a in low..high {
+                            for b in a + 1..high {
+                                found.push(with(chosen, &[sorted[a].1, sorted[b].1]));
+                            }
+                        }
+                        break;
+                    }
+                    Ordering::Equal => {
+                        let low_end = low + sorted[low..].iter()
+                            .take_while(|(v, _)| *v == low_value)
+                            .count();
+                        let high_start = high - sorted[..high].iter()
+                            .rev()
+                            .take_while(|(v, _)| *v == high_value)
+                            .count();
+                        for a in low..low_end {
+                            for b in high_start..high {
+                                found.push(with(chosen, &[sorted[a].1, sorted[b].1]));
+                            }
+                        }
+                        low = low_end;
+                        high = high_start;
+                    }
+                }
+            }
+        }
+        _ => {
+            for (n, (value, i)) in sorted.iter().enumerate() {
+                // Every later value is at least as big, so none of them fit either
+                if *value >= T::from(0) && exceeds(*value, k, target) {
+                    break;
+                }
+                let rest = target.checked_sub(*value).ok_or_else(|| overflow(target, '-', *value))?;
+                chosen.push(*i);
+                two_pointer(&sorted[n + 1..], k - 1, rest, chosen, found)?;
+                chosen.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+// Every combination is its k / 2 lowest indices followed by the rest, so
+// each lower half is only paired with upper halves starting after it
+fn meet_in_the_middle<T: Expense>(values: &[T], k: usize, target: T, found: &mut Vec<Vec<usize>>)
+                                  -> Result<()> {
+    let zero = T::from(0);
+    if k == 0 {
+        if target == zero {
+            found.push(Vec::new());
+        }
+        return Ok(());
+    }
+    // Without negative values sums only grow, so halves past the target can
+    // be left out
+    let limit = if values.iter().all(|v| *v >= zero) { Some(target) } else { None };
+    let mut lower_halves: HashMap<T, Vec<Vec<usize>>> = HashMap::new();
+    combinations(values, k / 2, limit, &mut Vec::new(), zero, &mut |indices, sum| {
+        lower_halves.entry(sum).or_default().push(indices.to_vec());
+    })?;
+    combinations(values, k - k / 2, limit, &mut Vec::new(), zero, &mut |upper, sum| {
+        // When the rest doesn't fit in T, no lower half adds up to it
+        let lower_halves = match target.checked_sub(sum).and_then(|rest| lower_halves.get(&rest)) {
+            Some(lower_halves) => lower_halves,
+            None => return,
+        };
+        for lower in lower_halves.iter().filter(|l| l.last().is_none_or(|i| *i < upper[0])) {
+            let mut combination = lower.clone();
+            combination.extend_from_slice(upper);
+            found.push(combination);
+        }
+    })
+}
+
+// Calls `f` with every combination of `size` indices, in increasing order,
+// whose values add up to no more than `limit`, if there is one
+fn combinations<T: Expense>(values: &[T], size: usize, limit: Option<T>, chosen: &mut Vec<usize>,
+                            sum: T, f: &mut dyn FnMut(&[usize], T)) -> Result<()> {
+    if chosen.len() == size {
+        f(chosen, sum);
+        return Ok(());
+    }
+    let start = chosen.last().map_or(0, |i| i + 1);
+    for i in start..values.len() {
+        let sum = match (sum.checked_add(values[i]), limit) {
+            (Some(sum), _) => sum,
+            // Only values that aren't negative have a limit, so this is past it
+            (None, Some(_)) => continue,
+            (None, None) => return Err(overflow(sum, '+', values[i])),
+        };
+        if limit.is_none_or(|limit| sum <= limit) {
+            chosen.push(i);
+            combinations(values, size, limit, chosen, sum, f)?;
+            chosen.pop();
+        }
+    }
+    Ok(())
+}
+
+/// How much memory `fewest_summing` may use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Memory {
+    // A table of which expense improved each sum: n × (target + 1) bits, for
+    // O(n·target) time
+    Table,
+    // Two rows of counts at a time, working out which expenses were used by
+    // splitting them in half and solving each half again: O(target) memory
+    // however many expenses there are, for O(n·target·log n) time
+    Bounded,
+}
+
+// More entries than any subset can have
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Finds the fewest expenses adding up to `target`, by dynamic programming
+/// over the sums up to it, and returns their line numbers (from 1) in order.
+/// When several subsets are equally small, either mode may pick any of them.
+/// Both modes keep a count for every sum up to `target`, and fail rather
+/// than abort if those don't fit in memory. Counting up through the sums
+/// doesn't work with refunds, so negative expenses are rejected.
+pub fn fewest_summing<T: Expense>(expenses: &[T], target: T, memory: Memory)
+                                  -> Result<Vec<usize>> {
+    let zero = T::from(0);
+    if let Some(refund) = expenses.iter().find(|e| **e < zero) {
+        return Err(Error::NoAnswer(format!("can't count up to a sum past a refund of {}", refund)));
+    }
+    let no_answer = || Error::NoAnswer(format!("no set of expenses sums to {}", target));
+    if target < zero {
+        return Err(no_answer());
+    }
+    let target_sum: u64 = target.try_into().map_err(|_| too_big(target))?;
+    // Expenses too big for a u64 are bigger than the target, so can't be used
+    let entries: Vec<(usize, u64)> = expenses.iter()
+        .enumerate()
+        .filter_map(|(i, e)| (*e).try_into().ok().map(|e| (i + 1, e)))
+        .collect();
+    if u128::from(target_sum) > entries.iter().map(|(_, e)| u128::from(*e)).sum() {
+        return Err(no_answer());
+    }
+    let width = usize::try_from(target_sum).ok()
+        .and_then(|target| target.checked_add(1))
+        .ok_or_else(|| too_big(target))?;
+    let mut lines = match memory {
+        Memory::Table => fewest_with_table(&entries, width)?,
+        Memory::Bounded => {
+            // Its own statement, so the row is dropped before recursing
+            let reachable = fewest_counts(&entries, width)?[width - 1] != UNREACHABLE;
+            if reachable {
+                let mut lines = Vec::new();
+                fewest_bounded(&entries, width - 1, &mut lines)?;
+                Some(lines)
+            } else {
+                None
+            }
+        }
+    }.ok_or_else(no_answer)?;
+    lines.sort_unstable();
+    debug!("{} expenses sum to {}: lines {:?}", lines.len(), target, lines);
+    Ok(lines)
+}
+
+fn too_big(target: impl Display) -> Error {
+    Error::NoAnswer(format!("the sums up to {} don't fit in memory", target))
+}
+
+// A row of `width` copies of `value`, or an error if there's no room for it
+fn filled<T: Clone>(width: usize, value: T) -> Result<Vec<T>> {
+    let mut row = Vec::new();
+    row.try_reserve_exact(width).map_err(|_| too_big(width as u64 - 1))?;
+    row.resize(width, value);
+    Ok(row)
+}
+
+// Adds one entry to a row of the fewest entries reaching each sum, calling
+// `improved` with each sum it reaches in fewer
+fn add_entry(fewest: &mut [u32], value: u64, mut improved: impl FnMut(usize)) {
+    if value >= fewest.len() as u64 {
+        return;
+    }
+    let value = value as usize;
+    for sum in (value..fewest.len()).rev() {
+        let without = fewest[sum - value];
+        if without != UNREACHABLE && without + 1 < fewest[sum] {
+            fewest[sum] = without + 1;
+            improved(sum);
+        }
+    }
+}
+
+// The fewest entries reaching each sum below `width`
+fn fewest_counts(entries: &[(usize, u64)], width: usize) -> Result<Vec<u32>> {
+    let mut fewest = filled(width, UNREACHABLE)?;
+    fewest[0] = 0;
+    for (_, value) in entries {
+        add_entry(&mut fewest, *value, |_| {});
+    }
+    Ok(fewest)
+}
+
+fn fewest_with_table(entries: &[(usize, u64)], width: usize) -> Result<Option<Vec<usize>>> {
+    let words = width.div_ceil(64);
+    let mut fewest = filled(width, UNREACHABLE)?;
+    fewest[0] = 0;
+    // One bit per entry and sum, set where the entry improved the sum
+    let bits = entries.len().checked_mul(words).ok_or_else(|| too_big(width as u64 - 1))?;
+    let mut improved_by = filled(bits, 0_u64)?;
+    for (n, (_, value)) in entries.iter().enumerate() {
+        let row = &mut improved_by[n * words..(n + 1) * words];
+        add_entry(&mut fewest, *value, |sum| row[sum / 64] |= 1 << (sum % 64));
+    }
+    if fewest[width - 1] == UNREACHABLE {
+        return Ok(None);
+    }
+    // The last entry to improve a sum is in its best subset, and the rest of
+    // that subset is the best for what's left using the entries before it
+    let mut lines = Vec::new();
+    let mut sum = width - 1;
+    for (n, (line, value)) in entries.iter().enumerate().rev() {
+        if improved_by[n * words + sum / 64] & (1 << (sum % 64)) != 0 {
+            lines.push(*line);
+            sum -= *value as usize;
+        }
+    }
+    Ok(Some(lines))
+}
+
+// Finds the best way to split the target between the two halves of the
+// entries, then solves each half for its share. Only called with targets the
+// entries can reach, whose rows are known to fit.
+fn fewest_bounded(entries: &[(usize, u64)], target: usize, lines: &mut Vec<usize>) -> Result<()> {
+    if target == 0 {
+        return Ok(());
+    }
+    if entries.len() == 1 {
+        lines.push(entries[0].0);
+        return Ok(());
+    }
+    let (left, right) = entries.split_at(entries.len() / 2);
+    // The rows are dropped before recursing, so only two are kept at a time
+    let split = {
+        let left_fewest = fewest_counts(left, target + 1)?;
+        let right_fewest = fewest_counts(right, target + 1)?;
+        (0..=target)
+            .filter(|s| left_fewest[*s] != UNREACHABLE && right_fewest[target - s] != UNREACHABLE)
+            .min_by_key(|s| left_fewest[*s] + right_fewest[target - s])
+            .unwrap()
+    };
+    fewest_bounded(left, split, lines)?;
+    fewest_bounded(right, target - split, lines)
+}
+
+#[test]
+fn test() {
+    let sample_data = vec![1721, 979, 366, 299, 675, 1456];
+    assert_eq!((1721, 299), summing_pair(&sample_data, 2020).unwrap());
+    assert_eq!((979, 366, 675), summing_triplet(&sample_data, 2020).unwrap());
+    assert_eq!((366, 299), summing_pair(&sample_data, 665).unwrap());
+    assert!(summing_pair(&sample_data, 10).is_err());
+}
+
+#[cfg(test)]
+fn brute_force_k_sum<T: Copy + Into<i128>>(values: &[T], k: usize, target: T) -> Vec<Vec<usize>> {
+    let mut found: Vec<Vec<usize>> = (0..1_u32 << values.len())
+        .filter(|mask| mask.count_ones() as usize == k)
+        .map(|mask| (0..values.len()).filter(|i| mask & (1 << i) != 0).collect::<Vec<usize>>())
+        .filter(|indices| indices.iter().map(|i| values[*i].into()).sum::<i128>() == target.into())
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+#[test]
+fn test_k_sum() {
+    let sample_data = vec![1721, 979, 366, 299, 675, 1456];
+    assert_eq!(k_sum(&sample_data, 2, 2020).unwrap(), vec![vec![0, 3]]);
+    assert_eq!(k_sum(&sample_data, 3, 2020).unwrap(), vec![vec![1, 2, 4]]);
+    assert_eq!(k_sum(&sample_data, 4, 1721 + 979 + 366 + 299).unwrap(), vec![vec![0, 1, 2, 3]]);
+    assert_eq!(k_sum(&[1010, 5, 1010, 1010], 2, 2020).unwrap(),
+               vec![vec![0, 2], vec![0, 3], vec![2, 3]]);
+    assert_eq!(k_sum(&sample_data, 0, 0).unwrap(), vec![Vec::<usize>::new()]);
+    assert!(k_sum(&sample_data, 0, 2020).unwrap().is_empty());
+    assert!(k_sum(&sample_data, 6, 2020).unwrap().is_empty());
+    assert!(k_sum(&sample_data, 7, 2020).is_err());
+    assert!(k_sum(&sample_data, usize::MAX, 2020).is_err());
+}
+
+#[test]
+fn test_k_sum_strategies_agree() {
+    let mut rng = aoc_common::Rng::new(1);
+    for _ in 0..200 {
+        let values: Vec<u32> = (0..rng.between(0, 12)).map(|_| rng.between(0, 20) as u32).collect();
+        let k = rng.between(0, 6) as usize;
+        let target = rng.between(0, 60) as u32;
+        for strategy in [Strategy::TwoPointer, Strategy::MeetInTheMiddle] {
+            match k_sum_with(&values, k, target, strategy) {
+                Ok(found) => assert_eq!(found, brute_force_k_sum(&values, k, target)),
+                Err(_) => assert!(k > values.len()),
+            }
+        }
+    }
+    // With refunds, which stop either strategy from cutting its search short
+    for _ in 0..200 {
+        let values: Vec<i64> = (0..rng.between(0, 12))
+            .map(|_| rng.between(0, 40) as i64 - 20)
+            .collect();
+        let k = rng.between(0, 6) as usize;
+        let target = rng.between(0, 80) as i64 - 40;
+        for strategy in [Strategy::TwoPointer, Strategy::MeetInTheMiddle] {
+            match k_sum_with(&values, k, target, strategy) {
+                Ok(found) => assert_eq!(found, brute_force_k_sum(&values, k, target)),
+                Err(_) => assert!(k > values.len()),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_no_expense_used_twice() {
+    // 1010 + 1010 only counts when there are two of them
+    assert!(summing_pair(&[1010, 5, 17], 2020).is_err());
+    assert_eq!(summing_pair(&[1010, 5, 1010], 2020).unwrap(), (1010, 1010));
+    // As does 673 + 673 + 674
+    assert!(summing_triplet(&[673, 674, 1000, 2], 2020).is_err());
+    assert_eq!(summing_triplet(&[673, 1000, 674, 673], 2020).unwrap(), (673, 674, 673));
+    assert!(summing_triplet(&[1010, 0, 3], 2020).is_err());
+    assert_eq!(summing_triplet(&[1010, 3, 0, 1010], 2020).unwrap(), (1010, 0, 1010));
+    assert!(summing_triplet(&[673, 673, 673], 2019).is_ok());
+    assert!(summing_triplet(&[673, 673], 2019).is_err());
+}
+
+#[test]
+fn test_triplet_matches_brute_force() {
+    let mut rng = aoc_common::Rng::new(2);
+    for _ in 0..500 {
+        let values: Vec<u32> = (0..rng.between(0, 12)).map(|_| rng.between(0, 20) as u32).collect();
+        let target = rng.between(0, 60) as u32;
+        match summing_triplet(&values, target) {
+            Ok((e1, e2, e3)) => assert_eq!(e1 + e2 + e3, target),
+            Err(_) => assert!(brute_force_k_sum(&values, 3, target).is_empty()),
+        }
+    }
+    // Values at the ends of the range, whose sums overflow on the way
+    let extremes = [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX];
+    for _ in 0..500 {
+        let values: Vec<i64> = (0..rng.between(0, 8)).map(|_| *rng.choose(&extremes)).collect();
+        let target = *rng.choose(&extremes);
+        match summing_triplet(&values, target) {
+            Ok((e1, e2, e3)) => assert_eq!(i128::from(e1) + i128::from(e2) + i128::from(e3),
+                                           i128::from(target)),
+            Err(_) => assert!(brute_force_k_sum(&values, 3, target).is_empty()),
+        }
+    }
+}
+
+#[test]
+fn test_fewest_summing() {
+    let sample_data = vec![1721, 979, 366, 299, 675, 1456];
+    for memory in [Memory::Table, Memory::Bounded] {
+        assert_eq!(fewest_summing(&sample_data, 2020, memory).unwrap(), vec![1, 4]);
+        assert_eq!(fewest_summing(&sample_data, 1456, memory).unwrap(), vec![6]);
+        assert_eq!(fewest_summing(&sample_data, 979 + 366 + 299, memory).unwrap(), vec![2, 3, 4]);
+        assert_eq!(fewest_summing(&sample_data, 0, memory).unwrap(), Vec::<usize>::new());
+        assert!(fewest_summing(&sample_data, 5, memory).is_err());
+        assert!(fewest_summing(&[], 5, memory).is_err());
+        // More than all the expenses together, so nothing is allocated for it
+        assert!(fewest_summing(&[1_u64, 2], 1_000_000_000_000, memory).is_err());
+        assert!(fewest_summing(&[1_u64, 2], u64::MAX, memory).is_err());
+        assert_eq!(fewest_summing(&[u128::MAX, 3, 4], 7, memory).unwrap(), vec![2, 3]);
+        assert!(fewest_summing(&[-5_i64, 10], 5, memory).is_err());
+        assert!(fewest_summing(&[5_i64, 10], -5, memory).is_err());
+    }
+}
+
+#[test]
+fn test_fewest_summing_matches_brute_force() {
+    let mut rng = aoc_common::Rng::new(3);
+    for _ in 0..300 {
+        let values: Vec<u32> = (0..rng.between(0, 12)).map(|_| rng.between(0, 30) as u32).collect();
+        let target = rng.between(0, 100) as u32;
+        let smallest = (0..=values.len())
+            .find(|k| !brute_force_k_sum(&values, *k, target).is_empty());
+        for memory in [Memory::Table, Memory::Bounded] {
+            match fewest_summing(&values, target, memory) {
+                Ok(lines) => {
+                    assert_eq!(Some(lines.len()), smallest);
+                    assert!(lines.windows(2).all(|w| w[0] < w[1]));
+                    assert_eq!(lines.iter().map(|l| values[l - 1]).sum::<u32>(), target);
+                }
+                Err(_) => assert_eq!(smallest, None),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_stream_pairs() {
+    use std::io::{self, Read};
+
+    // Fails once the lines before it have been read, like a log that's cut off
+    struct Broken;
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disconnected"))
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let input = io::BufReader::new("1010\n1721\n299\n1010\n1010\n".as_bytes().chain(Broken));
+    let read = stream_pairs(input, 2020, |pair| pairs.push((pair.first_line, pair.second_line)));
+    assert!(read.is_err());
+    assert_eq!(pairs, vec![(2, 3), (1, 4), (1, 5), (4, 5)]);
+
+    let mut found = 0;
+    assert_eq!(stream_pairs("1\n2\n".as_bytes(), 3, |_| found += 1).unwrap(), 2);
+    assert_eq!(found, 1);
+    // A bad line is skipped, and the lines after it are still paired up
+    let mut pairs = Vec::new();
+    let read = stream_pairs("1\nx\n2\n".as_bytes(), 3, |pair| pairs.push(pair.second_line));
+    assert_eq!(read.unwrap(), 2);
+    assert_eq!(pairs, vec![3]);
+}
+
+#[test]
+fn test_refunds() {
+    let day1 = Day1::<i64>::default();
+    let ledger = day1.parse_str("-500\n1721\n2520\n304\n-5\n").unwrap();
+    assert_eq!(summing_pair(&ledger, 2020).unwrap(), (-500, 2520));
+    assert_eq!(summing_triplet(&ledger, 2020).unwrap(), (1721, 304, -5));
+    assert_eq!(day1.part1(&ledger).unwrap(), -1_260_000);
+    assert_eq!(day1.part2(&ledger, &0).unwrap(), -2_615_920);
+    // Expenses bigger than the target used to underflow
+    assert_eq!(summing_triplet(&[2500_u32, 1000, 20, 1000], 2020).unwrap(), (1000, 20, 1000));
+}
+
+#[test]
+fn test_wide_ledger() {
+    let big = 10_u128.pow(30);
+    let day1 = Day1 { target: 2 * big };
+    let ledger = vec![big, 7, 2 * big - 7, big - 10, 10];
+    assert_eq!(day1.part1(&ledger).unwrap(), 7 * (2 * big - 7));
+    assert_eq!(summing_triplet(&ledger, 2 * big).unwrap(), (big, big - 10, 10));
+}
+
+#[test]
+fn test_overflow() {
+    // Products that don't fit are errors
+    let day1 = Day1::<i64> { target: 10_000_000_000 };
+    let error = day1.part1(&vec![5_000_000_000, 5_000_000_000]).unwrap_err();
+    assert_eq!(error.to_string(), "overflow: 5000000000 * 5000000000 doesn't fit");
+    let day1 = Day1::<i32> { target: 6000 };
+    assert!(matches!(day1.part2(&vec![2000, 2000, 2000], &0), Err(Error::Overflow(_))));
+    // Sums that overflow while searching just aren't the answer
+    assert_eq!(summing_triplet(&[1_u32, 2, 3, u32::MAX], 6).unwrap(), (1, 2, 3));
+    assert_eq!(summing_triplet(&[i64::MAX, 5, i64::MIN, 1], 0).unwrap(), (i64::MAX, i64::MIN, 1));
+    let error = summing_triplet(&[i64::MAX, i64::MAX, -1], 0).unwrap_err();
+    assert!(matches!(error, Error::NoAnswer(_)));
+    for strategy in [Strategy::TwoPointer, Strategy::MeetInTheMiddle] {
+        let found = k_sum_with(&[1_u32, 2, 3, u32::MAX], 3, 6, strategy).unwrap();
+        assert_eq!(found, vec![vec![0, 1, 2]]);
+    }
+}
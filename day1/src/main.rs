@@ -1,59 +1,49 @@
+use aoc_common::input::input_name;
+use aoc_common::run::{configure, parse_day_args_reading, DayArgs};
+use aoc_common::{info, log, open, run_day, Format, Result};
+use day1::Day1;
 use std::env;
-use std::fs;
-use std::io;
-use std::io::BufRead;
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let filename = &args[1];
-    println!("Reading file {}", filename);
-    let file = fs::File::open(filename).unwrap();
-    let reader = io::BufReader::new(file);
-    let mut expenses = Vec::new();
-    for (_, line) in reader.lines().enumerate() {
-        let expense: u32 = line.unwrap().parse().unwrap(); // Ignore errors.
-        expenses.push(expense);
-    }
-    let summing_pair = sum_to_2020_pair(&expenses);
-    println!("The pair is {}, {}", summing_pair.0, summing_pair.1);
-    println!("Product is {}", summing_pair.0 * summing_pair.1);
+const STREAM_USAGE: &str =
+    "Usage: day1 --stream [<input file> | -] [--set <name>=<value>]... [-v | -vv | -q]
 
+Reads expenses a line at a time, from stdin unless given an input file, and
+prints each pair adding up to the target as soon as its second expense arrives.";
 
-    let summing_triplet = sum_to_2020_triplet(&expenses);
-    println!("The triplet is {}, {}, {}", summing_triplet.0, summing_triplet.1, summing_triplet.2);
-    println!("Product is {}", summing_triplet.0 * summing_triplet.1 * summing_triplet.2);
-}
-
-fn sum_to_2020_pair(expenses: &Vec<u32>) -> (u32, u32) {
-    let expense_values :HashSet<u32> = HashSet::from_iter(expenses.iter().cloned());
-    for e1 in expenses.iter() {
-        let e2 = 2020 - e1;
-        if expense_values.contains(&e2) {
-            return (*e1, e2);
-        }
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) != Some("--stream") {
+        run_day(1, Day1::<i64>::default());
+        return;
     }
-    panic!("No match found")
-}
-
-
-fn sum_to_2020_triplet(expenses: &Vec<u32>) -> (u32, u32, u32) {
-    for (i, e1) in expenses.iter().enumerate() {
-        for (j, e2) in expenses.iter().skip(i).enumerate() {
-            for e3 in expenses.iter().skip(j) {
-                if (e1 + e2 + e3) == 2020 {
-                    println!("{} + {} + {} = 2020", e1, e2, e3);
-                    return (*e1, *e2, *e3);
-                }
-            }
+    args.next();
+    let args = match parse_day_args_reading(args, "-") {
+        Ok(args) if args.format != Format::Text => Err(String::from("--stream only prints text")),
+        parsed => parsed,
+    };
+    let args = match args {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, STREAM_USAGE);
+            process::exit(2);
         }
+    };
+    log::init(args.verbosity);
+    if let Err(e) = stream(&args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
     }
-    panic!("No match found")
 }
 
-#[test]
-fn test() {
-    let sample_data = vec![1721, 979, 366, 299, 675, 1456];
-    assert_eq!((1721, 299), sum_to_2020_pair(&sample_data));
+fn stream(args: &DayArgs) -> Result<()> {
+    let day1 = configure(Day1::<i64>::default(), &args.overrides)?;
+    let mut pairs = 0;
+    let expenses = day1::stream_pairs(open(&args.filename)?, day1.target, |pair| {
+        println!("Lines {} and {}: {} + {} = {}", pair.first_line, pair.second_line, pair.first,
+                 pair.second, day1.target);
+        pairs += 1;
+    }).map_err(|e| e.in_file(input_name(&args.filename)))?;
+    info!("Read {} expenses and found {} pairs", expenses, pairs);
+    Ok(())
 }
@@ -0,0 +1,99 @@
+//! Day 10: Adapter Array. Chains joltage adapters together.
+
+use aoc_common::{debug, read_numbers, Result, Solution};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<u64>> {
+        read_numbers(input)
+    }
+
+    fn part1(&self, lines: &Vec<u64>) -> Result<u64> {
+        Ok(run_part1(lines))
+    }
+
+    fn part2(&self, lines: &Vec<u64>, _: &u64) -> Result<u64> {
+        Ok(run_part2(lines))
+    }
+}
+
+/// Multiplies the number of 1-jolt differences by the number of 3-jolt
+/// differences in the chain of every adapter.
+pub fn run_part1(numbers: &[u64]) -> u64 {
+    let mut jolt_difference_counts: HashMap<u64, u64> = HashMap::new();
+    let mut previous_number = 0;
+    let mut sorted_numbers = numbers.to_vec();
+    sorted_numbers.sort();
+    jolt_difference_counts.insert(3, 1);
+    for (i, number) in sorted_numbers.iter().enumerate() {
+        if i == 0 {
+            jolt_difference_counts.insert(*number, 1);
+        } else {
+            let current_difference = number - previous_number;
+            let new_count = *jolt_difference_counts
+                .get(&current_difference)
+                .unwrap_or(&0)
+                + 1;
+            jolt_difference_counts.insert(current_difference, new_count);
+        }
+        previous_number = *number;
+    }
+    jolt_difference_counts.get(&1).cloned().unwrap_or(0)
+        * jolt_difference_counts.get(&3).cloned().unwrap_or(0)
+}
+
+/// Counts the distinct ways to arrange the adapters.
+pub fn run_part2(numbers: &[u64]) -> u64 {
+    let mut previous_number = 0;
+    let mut sorted_numbers = numbers.to_vec();
+    let mut run_length = 0;
+    let mut total = 1;
+    sorted_numbers.sort();
+    for number in sorted_numbers {
+        let current_difference = number - previous_number;
+        previous_number = number;
+        if current_difference == 1 {
+            run_length += 1
+        } else {
+            total *= match run_length {
+                4 => 7,
+                3 => 4,
+                2 => 2,
+                _ => 1,
+            };
+            run_length = 0;
+        }
+        if current_difference == 2 {
+            debug!("Found a difference of 2: {} - {}", number, previous_number);
+        }
+    }
+    total *= match run_length {
+        4 => 7,
+        3 => 4,
+        2 => 2,
+        _ => 1,
+    };
+    total
+}
+
+#[test]
+fn test() {
+    let lines = Day10.parse_file("sample_input_2.txt").unwrap();
+    let part_1_answer = run_part1(&lines);
+    // for (k, v) in part_1_answer {
+    //     println!("{} = {}", k, v);
+    // }
+    assert_eq!(part_1_answer, 220)
+}
+#[test]
+fn test_part2() {
+    let lines = Day10.parse_file("sample_input_2.txt").unwrap();
+    assert_eq!(run_part2(&lines), 19208);
+}
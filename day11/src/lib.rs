@@ -0,0 +1,251 @@
+//! Day 11: Seating System. Simulates people choosing seats.
+
+use aoc_common::{add, cancel, debug, read_char_grid, trace, Error, Result, Solution};
+use std::io::BufRead;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Vec<char>>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Vec<char>>> {
+        let seats = read_char_grid(input)?;
+        check_seats(&seats)?;
+        Ok(seats)
+    }
+
+    fn part1(&self, lines: &Vec<Vec<char>>) -> Result<u64> {
+        run_part1(lines)
+    }
+
+    fn part2(&self, lines: &Vec<Vec<char>>, _: &u64) -> Result<u64> {
+        run_part2(lines)
+    }
+}
+
+/// Checks that every position is floor (`.`), an empty seat (`L`) or an
+/// occupied one (`#`).
+pub fn check_seats(seats: &[Vec<char>]) -> Result<()> {
+    for (i, row) in seats.iter().enumerate() {
+        let line: String = row.iter().collect();
+        if let Some((position, status)) = line.char_indices().find(|(_, c)| !".L#".contains(*c)) {
+            let text = &line[position..position + status.len_utf8()];
+            return Err(Error::parse(i + 1, &line, text, "'.', 'L' or '#'"));
+        }
+    }
+    Ok(())
+}
+
+/// Counts the occupied seats once nothing changes, when people look at
+/// adjacent seats. Fails if it's cancelled first.
+pub fn run_part1(seats: &[Vec<char>]) -> Result<u64> {
+    let mut last_round_result = apply_round1(seats);
+    let mut occupied_seats = 0;
+    let mut iterations = 0;
+    let mut changed = true;
+    while changed {
+        cancel::check()?;
+        let current_result = apply_round2(&last_round_result);
+        let current_occupied_seats = count_occupied(&current_result);
+        iterations += 1;
+        last_round_result = current_result;
+        changed = occupied_seats != current_occupied_seats;
+        occupied_seats = current_occupied_seats;
+    }
+    debug!("Completed after {} iterations", iterations);
+    Ok(occupied_seats)
+}
+
+/// Counts the occupied seats once nothing changes, when people look at the
+/// first seat they can see in each direction. Fails if it's cancelled first.
+pub fn run_part2(seats: &[Vec<char>]) -> Result<u64> {
+    let mut last_round_result = apply_round1(seats);
+    let mut occupied_seats = 0;
+    let mut iterations = 0;
+    let mut changed = true;
+    while changed {
+        cancel::check()?;
+        let current_result = apply_round2_part2(&last_round_result);
+        let current_occupied_seats = count_occupied(&current_result);
+        iterations += 1;
+        last_round_result = current_result;
+        changed = occupied_seats != current_occupied_seats;
+        occupied_seats = current_occupied_seats;
+    }
+    debug!("Completed after {} iterations", iterations);
+    Ok(occupied_seats)
+}
+
+pub fn count_occupied(seats: &[Vec<char>]) -> u64 {
+    let mut occupied_count = 0;
+    for row in seats.iter() {
+        for status in row.iter() {
+            if *status == '#' {
+                occupied_count += 1;
+            }
+        }
+    }
+    occupied_count
+}
+
+fn apply_round1(seats: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut result = Vec::new();
+    for (i, row) in seats.iter().enumerate() {
+        for (j, status) in row.iter().enumerate() {
+            if result.get(i).is_none() {
+                result.insert(i, Vec::new());
+            }
+            let result_row = result.get_mut(i).unwrap();
+            if *status == 'L' {
+                result_row.insert(j, '#');
+            } else {
+                result_row.insert(j, *status);
+            }
+        }
+    }
+    result
+}
+
+fn apply_round2(seats: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut result = Vec::new();
+    for (i, row) in seats.iter().enumerate() {
+        for (j, status) in row.iter().enumerate() {
+            if result.get(i).is_none() {
+                result.insert(i, Vec::new());
+            }
+            let result_row = result.get_mut(i).unwrap();
+            let adjacent_occupied_seats = adjacent_occupied_count(i, j, seats);
+            if *status == '#' && adjacent_occupied_seats >= 4 {
+                result_row.insert(j, 'L');
+            } else if *status == 'L' && adjacent_occupied_seats == 0 {
+                result_row.insert(j, '#');
+            } else {
+                result_row.insert(j, *status);
+            }
+        }
+    }
+    result
+}
+
+fn apply_round2_part2(seats: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut result = Vec::new();
+    for (i, row) in seats.iter().enumerate() {
+        for (j, status) in row.iter().enumerate() {
+            if result.get(i).is_none() {
+                result.insert(i, Vec::new());
+            }
+            let result_row = result.get_mut(i).unwrap();
+            let visible_occupied_seats = visible_occupied_count(i, j, seats);
+            if *status == '#' && visible_occupied_seats >= 5 {
+                result_row.insert(j, 'L');
+            } else if *status == 'L' && visible_occupied_seats == 0 {
+                result_row.insert(j, '#');
+            } else {
+                result_row.insert(j, *status);
+            }
+        }
+    }
+    result
+}
+
+fn adjacent_occupied_count(i: usize, j: usize, seats: &[Vec<char>]) -> usize {
+    let occupied_count = is_occupied(i as isize - 1, j as isize, seats)
+        + is_occupied(i as isize + 1, j as isize, seats)
+        + is_occupied(i as isize, j as isize + 1, seats)
+        + is_occupied(i as isize - 1, j as isize + 1, seats)
+        + is_occupied(i as isize + 1, j as isize + 1, seats)
+        + is_occupied(i as isize, j as isize - 1, seats)
+        + is_occupied(i as isize - 1, j as isize - 1, seats)
+        + is_occupied(i as isize + 1, j as isize - 1, seats);
+    trace!("{} adjacent to {}, {} are occupied", occupied_count, i, j);
+    occupied_count
+}
+
+fn is_occupied(i: isize, j: isize, seats: &[Vec<char>]) -> usize {
+    has_status(i, j, '#', seats)
+}
+
+fn has_status(i: isize, j: isize, status: char, seats: &[Vec<char>]) -> usize {
+    let empty_vec = Vec::new();
+    if i < 0 || j < 0 {
+        return 0;
+    }
+    (seats
+        .get(i as usize)
+        .unwrap_or(&empty_vec)
+        .get(j as usize)
+        .cloned()
+        .unwrap_or('_')
+        == status) as usize
+}
+
+fn visible_occupied_count(i: usize, j: usize, seats: &[Vec<char>]) -> usize {
+    has_visible_seat_occupied(i, j, 0, 1, seats)
+        + has_visible_seat_occupied(i, j, 1, 0, seats)
+        + has_visible_seat_occupied(i, j, 1, 1, seats)
+        + has_visible_seat_occupied(i, j, 0, -1, seats)
+        + has_visible_seat_occupied(i, j, -1, 0, seats)
+        + has_visible_seat_occupied(i, j, -1, -1, seats)
+        + has_visible_seat_occupied(i, j, 1, -1, seats)
+        + has_visible_seat_occupied(i, j, -1, 1, seats)
+}
+
+fn has_visible_seat_occupied(
+    i: usize,
+    j: usize,
+    xslope: i32,
+    yslope: i32,
+    seats: &[Vec<char>],
+) -> usize {
+    let mut x = add(i, xslope);
+    let mut y = add(j, yslope);
+    while x.is_some() && y.is_some() {
+        let x_value = x.unwrap() as isize;
+        let y_value = y.unwrap() as isize;
+        if x.unwrap() >= seats.len() || y.unwrap() >= seats.get(x.unwrap()).unwrap().len() {
+            return 0;
+        }
+        if is_occupied(x_value, y_value, seats) == 1 {
+            return 1;
+        } else if has_status(x_value, y_value, 'L', seats) == 1 {
+            return 0;
+        }
+        x = add(x.unwrap(), xslope);
+        y = add(y.unwrap(), yslope);
+    }
+    0
+}
+
+#[test]
+fn test_part1() {
+    let lines = Day11.parse_file("sample_input.txt").unwrap();
+    assert_eq!(run_part1(&lines).unwrap(), 37);
+}
+
+#[test]
+fn test_part2() {
+    let lines = Day11.parse_file("sample_input.txt").unwrap();
+    assert_eq!(run_part2(&lines).unwrap(), 26);
+}
+
+#[test]
+fn test_visible_occupied_count() {
+    let example2 = Day11.parse_file("sample_input_2.txt").unwrap();
+    assert_eq!(visible_occupied_count(4, 3, &example2), 8);
+    let example3 = Day11.parse_file("sample_input_3.txt").unwrap();
+    assert_eq!(visible_occupied_count(1, 1, &example3), 0);
+    let example4 = Day11.parse_file("sample_input_4.txt").unwrap();
+    assert_eq!(visible_occupied_count(3, 3, &example4), 0);
+}
+
+#[test]
+fn test_check_seats() {
+    assert!(check_seats(&[vec!['L', '.', '#']]).is_ok());
+    let error = check_seats(&[vec!['L', 'L'], vec!['L', 'x']]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:2:2: expected '.', 'L' or '#', found \"x\""
+    );
+}
@@ -0,0 +1,291 @@
+//! Day 12: Rain Risk. Follows the ship's navigation instructions.
+
+use aoc_common::{parse_at, read_lines, Error, Solution};
+use regex::Regex;
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A compass direction the ship or waypoint can face.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    E,
+    S,
+    N,
+    W,
+}
+
+/// A navigation action: move north, south, east or west, turn left or
+/// right, or go forward.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    E,
+    S,
+    N,
+    W,
+    L,
+    R,
+    F,
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Action, ()> {
+        match s {
+            "E" => Ok(Action::E),
+            "S" => Ok(Action::S),
+            "N" => Ok(Action::N),
+            "W" => Ok(Action::W),
+            "L" => Ok(Action::L),
+            "R" => Ok(Action::R),
+            "F" => Ok(Action::F),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Action {
+    /// The direction an N, S, E or W action moves in.
+    pub fn to_direction(self) -> Option<Direction> {
+        match self {
+            Action::E => Option::Some(Direction::E),
+            Action::S => Option::Some(Direction::S),
+            Action::W => Option::Some(Direction::W),
+            Action::N => Option::Some(Direction::N),
+            _ => Option::None,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Direction, ()> {
+        match s {
+            "E" => Ok(Direction::E),
+            "S" => Ok(Direction::S),
+            "N" => Ok(Direction::N),
+            "W" => Ok(Direction::W),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Direction::E => "E",
+                Direction::N => "N",
+                Direction::S => "S",
+                Direction::W => "W",
+            }
+        )
+    }
+}
+impl Direction {
+    /// Turns clockwise by a multiple of 90 degrees, or anticlockwise if negative.
+    pub fn rotate(&self, degrees: i32) -> Direction {
+        let directions: Vec<Direction> =
+            vec![Direction::E, Direction::S, Direction::W, Direction::N];
+        let current_face_index = directions.iter().position(|d| d == self).unwrap() as i32;
+        directions[(current_face_index + degrees / 90).rem_euclid(4) as usize]
+    }
+
+    /// The sign of movement in this direction, with north and east positive.
+    pub fn multiplier(&self) -> i32 {
+        match self {
+            Direction::S | Direction::W => -1,
+            _ => 1,
+        }
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> aoc_common::Result<Vec<Instruction>> {
+        to_instructions(&read_lines(input)?)
+    }
+
+    fn part1(&self, instructions: &Vec<Instruction>) -> aoc_common::Result<u64> {
+        Ok(run_part1(instructions))
+    }
+
+    fn part2(&self, instructions: &Vec<Instruction>, _: &u64) -> aoc_common::Result<u64> {
+        Ok(run_part2(instructions))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub action: Action,
+    // A distance, or degrees for L and R
+    pub value: i32,
+}
+
+impl Instruction {
+    fn parse(
+        line_number: usize,
+        line: &str,
+        line_regex: &Regex,
+    ) -> aoc_common::Result<Instruction> {
+        let captures = match line_regex.captures(line) {
+            Some(captures) => captures,
+            None => {
+                return Err(Error::parse(
+                    line_number,
+                    line,
+                    line,
+                    "an action and a value",
+                ))
+            }
+        };
+        let action_text = captures.name("action").unwrap().as_str();
+        let action = action_text.parse::<Action>().map_err(|_| {
+            Error::parse(
+                line_number,
+                line,
+                action_text,
+                "one of N, S, E, W, L, R or F",
+            )
+        })?;
+        let value_text = captures.name("value").unwrap().as_str();
+        let value: i32 = parse_at(line_number, line, value_text, "a number")?;
+        if (action == Action::L || action == Action::R) && value % 90 != 0 {
+            return Err(Error::parse(
+                line_number,
+                line,
+                value_text,
+                "a multiple of 90 degrees",
+            ));
+        }
+        Ok(Instruction { action, value })
+    }
+}
+
+/// Parses lines like "F10" or "R90".
+pub fn to_instructions(lines: &[String]) -> aoc_common::Result<Vec<Instruction>> {
+    let line_regex = Regex::new(r"^(?P<action>\w)(?P<value>.*)$").unwrap();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| Instruction::parse(i + 1, line, &line_regex))
+        .collect()
+}
+
+/// The Manhattan distance the ship ends up from its start, when actions move
+/// the ship itself.
+pub fn run_part1(instructions: &[Instruction]) -> u64 {
+    let mut face = Direction::E;
+    let mut east_west_position: i32 = 0;
+    let mut north_south_position: i32 = 0;
+    for &Instruction { action, value } in instructions {
+        match action {
+            Action::F => match face {
+                Direction::N | Direction::S => north_south_position += face.multiplier() * value,
+                Direction::E | Direction::W => east_west_position += face.multiplier() * value,
+            },
+            Action::N | Action::S => {
+                north_south_position += action.to_direction().unwrap().multiplier() * value
+            }
+            Action::E | Action::W => {
+                east_west_position += action.to_direction().unwrap().multiplier() * value
+            }
+            Action::R => face = face.rotate(value),
+            Action::L => face = face.rotate(-value),
+        }
+    }
+    let manhattan_distance = east_west_position.abs() + north_south_position.abs();
+    manhattan_distance as u64
+}
+
+/// The Manhattan distance the ship ends up from its start, when actions move
+/// a waypoint that the ship follows.
+pub fn run_part2(instructions: &[Instruction]) -> u64 {
+    // Always keep the E/W direction in position 0, and N/S in position 1 to simplify action processing
+    let mut waypoint_face = (Direction::E, Direction::N);
+    let mut waypoint = (10, 1);
+    let mut east_west_position: i32 = 0;
+    let mut north_south_position: i32 = 0;
+    for &Instruction { action, value } in instructions {
+        match action {
+            Action::F => {
+                east_west_position += waypoint.0 * waypoint_face.0.multiplier() * value;
+                north_south_position += waypoint.1 * waypoint_face.1.multiplier() * value;
+            }
+            Action::N | Action::S => {
+                waypoint.1 += if waypoint_face.1 == action.to_direction().unwrap() {
+                    1
+                } else {
+                    -1
+                } * value;
+            }
+            Action::E | Action::W => {
+                waypoint.0 += if waypoint_face.0 == action.to_direction().unwrap() {
+                    1
+                } else {
+                    -1
+                } * value;
+            }
+            Action::L | Action::R => {
+                let multiplier = if action == Action::L { -1 } else { 1 };
+                let new_face = (
+                    waypoint_face.0.rotate(multiplier * value),
+                    waypoint_face.1.rotate(multiplier * value),
+                );
+                if new_face.0 == Direction::N || new_face.0 == Direction::S {
+                    waypoint = (waypoint.1, waypoint.0);
+                    waypoint_face = (new_face.1, new_face.0);
+                } else {
+                    waypoint_face = new_face;
+                }
+            }
+        }
+    }
+    let manhattan_distance = east_west_position.abs() + north_south_position.abs();
+    manhattan_distance as u64
+}
+
+#[test]
+fn test_part1() {
+    let instructions = Day12.parse_file("sample_input.txt").unwrap();
+    assert_eq!(run_part1(&instructions), 25);
+}
+#[test]
+fn test_part2() {
+    let instructions = Day12.parse_file("sample_input.txt").unwrap();
+    assert_eq!(run_part2(&instructions), 286);
+    let input = Day12.parse_file("input.txt").unwrap();
+    assert_eq!(run_part2(&input), 26841);
+}
+
+#[test]
+fn test_rotate() {
+    assert_eq!(Direction::E.rotate(90), Direction::S);
+    assert_eq!(Direction::E.rotate(-90), Direction::N);
+    assert_eq!(Direction::E.rotate(180), Direction::W);
+    assert_eq!(Direction::E.rotate(-270), Direction::S);
+    assert_eq!(Direction::E.rotate(270), Direction::N);
+}
+
+#[test]
+fn test_invalid_instruction() {
+    let error = Day12.parse_str("F10\nX3").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:2:1: expected one of N, S, E, W, L, R or F, found \"X\""
+    );
+    let error = Day12.parse_str("R45").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:2: expected a multiple of 90 degrees, found \"45\""
+    );
+}
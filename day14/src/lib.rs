@@ -0,0 +1,176 @@
+//! Day 14: Docking Data. Runs the ferry's bitmask initialization program.
+
+use aoc_common::{cancel, parse_at, read_lines, trace, Error, Result, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Instruction>> {
+        to_instructions(&read_lines(input)?)
+    }
+
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<u64> {
+        Ok(run_part1(instructions))
+    }
+
+    fn part2(&self, instructions: &Vec<Instruction>, _: &u64) -> Result<u64> {
+        run_part2(instructions)
+    }
+}
+
+/// A line of the initialization program.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Instruction {
+    // 36 characters, each '0', '1' or 'X', most significant bit first
+    Mask(String),
+    Write { addr: u64, value: u64 },
+}
+
+/// Parses lines like `mask = XXXX1X0` or `mem[8] = 11`.
+pub fn to_instructions(lines: &[String]) -> Result<Vec<Instruction>> {
+    let instruction_re = Regex::new(r"^(?P<instr>.*) = (?P<value>\w+)$").unwrap();
+    let mem_re = Regex::new(r"^mem\[(?P<addr>.*)\]$").unwrap();
+    let mut instructions = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let instruction_capture = instruction_re.captures(line).ok_or_else(|| {
+            Error::parse(
+                i + 1,
+                line,
+                line,
+                "'mask = <mask>' or 'mem[<addr>] = <value>'",
+            )
+        })?;
+        let instruction = instruction_capture.name("instr").unwrap().as_str();
+        let value = instruction_capture.name("value").unwrap().as_str();
+        if instruction == "mask" {
+            if value.len() != 36 || value.chars().any(|c| !"01X".contains(c)) {
+                return Err(Error::parse(i + 1, line, value, "36 bits of 0, 1 or X"));
+            }
+            instructions.push(Instruction::Mask(String::from(value)));
+        } else {
+            let addr = match mem_re.captures(instruction) {
+                Some(mem_capture) => mem_capture.name("addr").unwrap().as_str(),
+                None => {
+                    return Err(Error::parse(
+                        i + 1,
+                        line,
+                        instruction,
+                        "mask or mem[<addr>]",
+                    ))
+                }
+            };
+            instructions.push(Instruction::Write {
+                addr: parse_at(i + 1, line, addr, "a memory address")?,
+                value: parse_at(i + 1, line, value, "a number")?,
+            });
+        }
+    }
+    Ok(instructions)
+}
+
+/// Sums memory after running the program, with masks applied to values.
+pub fn run_part1(instructions: &[Instruction]) -> u64 {
+    let mut mask_to_and: u64 = 1;
+    let mut mask_to_or: u64 = 0;
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(mask) => {
+                // Masks are checked when parsing, so these are always valid binary
+                mask_to_and = u64::from_str_radix(&mask.replace("X", "1"), 2).unwrap();
+                mask_to_or = u64::from_str_radix(&mask.replace("X", "0"), 2).unwrap();
+            }
+            Instruction::Write { addr, value } => {
+                mem.insert(*addr, value & mask_to_and | mask_to_or);
+            }
+        }
+    }
+    mem.values().sum()
+}
+
+/// Sums memory after running the program, with masks applied to addresses and
+/// `X` bits writing to both possible addresses. A mask with many `X` bits
+/// writes to a great many addresses, so this fails if it's cancelled first.
+pub fn run_part2(instructions: &[Instruction]) -> Result<u64> {
+    let mut mask: &str = "";
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    for instruction in instructions {
+        match instruction {
+            Instruction::Mask(new_mask) => mask = new_mask,
+            Instruction::Write { addr, value } => {
+                let addr = format!("{:036b}", addr);
+                let floating_bits = count_xs(mask);
+                let mask_chars: Vec<char> = mask.chars().collect();
+                for i in 0..2_u64.pow(floating_bits as u32) {
+                    cancel::check()?;
+                    let mut to_change: Vec<char> = format!("{:0width$b}", i, width = floating_bits)
+                        .chars()
+                        .collect();
+                    let mut addr_to_write = String::new();
+                    for (j, c) in addr.chars().enumerate() {
+                        match mask_chars.get(j) {
+                            Some('1') => addr_to_write.push('1'),
+                            Some('X') => addr_to_write.push(to_change.pop().unwrap_or('0')),
+                            _ => addr_to_write.push(c),
+                        }
+                    }
+                    trace!("Writing to {}", addr_to_write);
+                    let decimal_addr = u64::from_str_radix(addr_to_write.as_str(), 2).unwrap();
+                    mem.insert(decimal_addr, *value);
+                }
+            }
+        }
+    }
+    Ok(mem.values().sum())
+}
+
+fn count_xs(l: &str) -> usize {
+    l.replace("0", "").replace("1", "").len()
+}
+
+#[test]
+fn test_part1() {
+    let instructions = Day14.parse_file("sample_input.txt").unwrap();
+    assert_eq!(run_part1(&instructions), 165);
+}
+
+#[test]
+fn test_part2() {
+    let instructions = Day14.parse_file("sample_input_2.txt").unwrap();
+    assert_eq!(run_part2(&instructions).unwrap(), 208);
+}
+
+#[cfg(test)]
+use aoc_common::open;
+
+#[test]
+fn test_masks() {
+    let lines = read_lines(open("masks.txt").unwrap()).unwrap();
+    let max_xs = lines
+        .iter()
+        .map(|l| l.replace("0", "").replace("1", "").len())
+        .max()
+        .unwrap();
+    println!("Max is {}", max_xs);
+}
+
+#[test]
+fn test_invalid_instruction() {
+    let error = Day14.parse_str("mask = 10X").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:8: expected 36 bits of 0, 1 or X, found \"10X\""
+    );
+    let error = Day14.parse_str("mem[a] = 1").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:5: expected a memory address, found \"a\""
+    );
+}
@@ -0,0 +1,106 @@
+//! Day 15: Rambunctious Recitation. Plays the elves' memory game.
+
+use aoc_common::{cancel, parse_at, read_lines, trace, Config, Result, Solution};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+pub struct Day15 {
+    // Which number spoken to report for each part, counting from 1
+    pub part1_turns: u64,
+    pub part2_turns: u64,
+}
+
+impl Default for Day15 {
+    fn default() -> Day15 {
+        Day15 {
+            part1_turns: 2020,
+            part2_turns: 30_000_000,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<u64>> {
+        let lines = read_lines(input)?;
+        let line = lines.first().map(String::as_str).unwrap_or_default();
+        to_starting_numbers(line)
+    }
+
+    fn part1(&self, starting_numbers: &Vec<u64>) -> Result<u64> {
+        nth_spoken_number(starting_numbers, self.part1_turns)
+    }
+
+    fn part2(&self, starting_numbers: &Vec<u64>, _: &u64) -> Result<u64> {
+        nth_spoken_number(starting_numbers, self.part2_turns)
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.part1_turns = config.get("part1_turns", self.part1_turns, "a number of turns")?;
+        self.part2_turns = config.get("part2_turns", self.part2_turns, "a number of turns")?;
+        Ok(())
+    }
+}
+
+/// Parses a comma-separated list like "0,3,6".
+pub fn to_starting_numbers(line: &str) -> Result<Vec<u64>> {
+    line.split(',')
+        .map(|v| parse_at(1, line, v, "a number"))
+        .collect()
+}
+
+/// Plays the memory game and returns the number spoken on turn `n`. Fails if
+/// it's cancelled first.
+pub fn nth_spoken_number(starting_numbers: &[u64], n: u64) -> Result<u64> {
+    let mut history: HashMap<u64, u64> = HashMap::new();
+    let mut last_number_spoken = 0;
+    let mut last_time_spoken: Option<u64> = Option::None;
+    for turn in 0..n {
+        // Checking on every turn would slow the game down noticeably
+        if turn % 65_536 == 0 {
+            cancel::check()?;
+        }
+        if turn > 0 {
+            last_time_spoken = history.insert(last_number_spoken, turn);
+        }
+        if turn < starting_numbers.len() as u64 {
+            last_number_spoken = *starting_numbers.get(turn as usize).unwrap();
+        } else {
+            if let Some(last_time) = last_time_spoken {
+                last_number_spoken = turn - last_time;
+            } else {
+                last_number_spoken = 0;
+            }
+        }
+        trace!("{}: {}", turn, last_number_spoken);
+    }
+    Ok(last_number_spoken)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(nth_spoken_number(&[0, 3, 6], 10).unwrap(), 0);
+    assert_eq!(nth_spoken_number(&[0, 3, 6], 2020).unwrap(), 436);
+    assert_eq!(nth_spoken_number(&[1, 3, 2], 2020).unwrap(), 1);
+}
+
+#[test]
+fn test_to_starting_numbers() {
+    assert_eq!(to_starting_numbers("0,3,6").unwrap(), vec![0, 3, 6]);
+    let error = to_starting_numbers("0,3,,6").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:5: expected a number, found \"\""
+    );
+    assert!(to_starting_numbers("").is_err());
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(nth_spoken_number(&[0, 3, 6], 30_000_000).unwrap(), 175594);
+    assert_eq!(nth_spoken_number(&[1, 3, 2], 30_000_000).unwrap(), 2578);
+    assert_eq!(nth_spoken_number(&[0, 3, 6], 2020).unwrap(), 436);
+}
@@ -0,0 +1,349 @@
+//! Day 16: Ticket Translation. Works out the fields on train tickets.
+
+use aoc_common::{cancel, parse_at, read_records, Config, Error, Record, Result, Solution};
+use regex::Regex;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+/// The ticket notes: the field rules, your ticket and the nearby tickets.
+#[derive(Debug)]
+pub struct InputData {
+    pub rules: Vec<Rule>,
+    pub my_ticket: Vec<u64>,
+    pub nearby_tickets: Vec<Vec<u64>>,
+}
+
+/// A ticket field and the two ranges its values can be in.
+#[derive(Debug)]
+pub struct Rule {
+    pub field_name: String,
+    pub low_range: RangeInclusive<u64>,
+    pub high_range: RangeInclusive<u64>,
+}
+
+impl Rule {
+    pub fn is_valid(&self, value: &u64) -> bool {
+        self.low_range.contains(value) || self.high_range.contains(value)
+    }
+}
+
+pub struct Day16 {
+    // Part 2 multiplies together the values of fields whose names start with this
+    pub field_prefix: String,
+}
+
+impl Default for Day16 {
+    fn default() -> Day16 {
+        Day16 {
+            field_prefix: String::from("departure"),
+        }
+    }
+}
+
+impl Solution for Day16 {
+    type Input = InputData;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<InputData> {
+        to_input(input)
+    }
+
+    fn part1(&self, input_data: &InputData) -> Result<u64> {
+        nearby_error_rate(input_data)
+    }
+
+    fn part2(&self, input_data: &InputData, _: &u64) -> Result<u64> {
+        part_2(input_data, &self.field_prefix)
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.field_prefix = config.get(
+            "field_prefix",
+            self.field_prefix.clone(),
+            "a field name prefix",
+        )?;
+        Ok(())
+    }
+}
+
+pub fn to_input<R: BufRead>(input: R) -> Result<InputData> {
+    let mut records = read_records(input)?.into_iter();
+    let rules = parse_rules(&records.next().unwrap())?;
+    // Skip the "your ticket:" and "nearby tickets:" headers
+    let mut my_ticket = ticket_section(records.next(), "your ticket:", rules.len())?;
+    let my_ticket = my_ticket.pop().ok_or_else(|| missing("your ticket"))?;
+    let nearby_tickets = ticket_section(records.next(), "nearby tickets:", rules.len())?;
+    Ok(InputData {
+        rules,
+        my_ticket,
+        nearby_tickets,
+    })
+}
+
+fn missing(section: &str) -> Error {
+    Error::NoAnswer(format!("the notes have no {}", section))
+}
+
+// Reads the tickets after the header, each with one value per field
+fn ticket_section(record: Option<Record>, header: &str, width: usize) -> Result<Vec<Vec<u64>>> {
+    let record = record.ok_or_else(|| missing(header.trim_end_matches(':')))?;
+    let first_line = record.lines.first().map(String::as_str).unwrap_or_default();
+    if first_line != header {
+        return Err(Error::parse(
+            record.line,
+            first_line,
+            first_line,
+            &format!("{:?}", header),
+        ));
+    }
+    record
+        .lines
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, line)| {
+            let ticket: Vec<u64> = line
+                .split(',')
+                .map(|v| parse_at(record.line + i, line, v, "a field value"))
+                .collect::<Result<_>>()?;
+            if ticket.len() != width {
+                let expected = format!("a ticket with {} fields", width);
+                return Err(Error::parse(record.line + i, line, line, &expected));
+            }
+            Ok(ticket)
+        })
+        .collect()
+}
+
+/// Sums the values on nearby tickets that don't match any rule.
+pub fn nearby_error_rate(input_data: &InputData) -> Result<u64> {
+    let rules = &input_data.rules;
+    let mut error_rate: u64 = 0;
+    for field_values in &input_data.nearby_tickets {
+        for value in field_values {
+            if !rules.iter().any(|rule| rule.is_valid(value)) {
+                error_rate = error_rate.checked_add(*value).ok_or_else(|| {
+                    Error::Overflow(format!("{} + {} doesn't fit", error_rate, value))
+                })?;
+            }
+        }
+    }
+    Ok(error_rate)
+}
+
+/// Multiplies together the values on your ticket of the fields whose names
+/// start with `field_prefix`.
+pub fn part_2(input_data: &InputData, field_prefix: &str) -> Result<u64> {
+    let ordered_fields = get_ordered_fields(input_data)?;
+    let my_ticket_fields = &input_data.my_ticket;
+    let mut ans: u64 = 1;
+    for (i, field) in ordered_fields.iter().enumerate() {
+        if field.starts_with(field_prefix) {
+            let value = my_ticket_fields.get(i).ok_or_else(|| {
+                Error::NoAnswer(format!("your ticket has no value for {}", field))
+            })?;
+            ans = ans
+                .checked_mul(*value)
+                .ok_or_else(|| Error::Overflow(format!("{} * {} doesn't fit", ans, value)))?;
+        }
+    }
+    Ok(ans)
+}
+
+/// Works out which field each position on the tickets holds, failing if the
+/// rules don't narrow each position down to one field.
+pub fn get_ordered_fields(input_data: &InputData) -> Result<Vec<String>> {
+    let rules = &input_data.rules;
+    let valid_tickets = get_valid_tickets(&input_data.nearby_tickets, rules);
+    let field_count = rules.len();
+    let mut field_possible_values: Vec<HashSet<String>> = Vec::new();
+    let mut ordered_fields = vec![String::new(); field_count];
+
+    for _ in 0..field_count {
+        let possible_values: HashSet<String> = rules.iter().map(|r| r.field_name.clone()).collect();
+        field_possible_values.push(possible_values);
+    }
+
+    for field_values in valid_tickets {
+        for (idx, value) in field_values.iter().enumerate() {
+            if rules.iter().any(|rule| rule.is_valid(value)) {
+                for rule in rules {
+                    if !rule.is_valid(value) {
+                        let mut new_possible_values =
+                            field_possible_values.get(idx).unwrap().clone();
+                        new_possible_values.remove(&rule.field_name);
+                        field_possible_values.remove(idx);
+                        field_possible_values.insert(idx, new_possible_values)
+                    }
+                }
+            }
+        }
+    }
+    let mut matched = 0;
+    while matched < field_count {
+        cancel::check()?;
+        for (idx, field_names) in field_possible_values.iter_mut().enumerate() {
+            let matched_values: HashSet<String> = ordered_fields.iter().cloned().collect();
+            let unmatched: HashSet<String> =
+                field_names.difference(&matched_values).cloned().collect();
+            if unmatched.len() == 1 {
+                ordered_fields.remove(idx);
+                let value = unmatched.iter().next().unwrap().clone();
+                ordered_fields.insert(idx, value.clone());
+            }
+        }
+        let now_matched = ordered_fields.iter().filter(|v| !v.is_empty()).count();
+        // Another pass would see the same candidates and fix nothing either
+        if now_matched == matched {
+            return Err(Error::NoAnswer(format!(
+                "the rules fit {} of the {} positions to more than one field",
+                field_count - matched,
+                field_count
+            )));
+        }
+        matched = now_matched;
+    }
+    Ok(ordered_fields)
+}
+
+pub fn get_valid_tickets<'a>(all_tickets: &'a [Vec<u64>], rules: &[Rule]) -> Vec<&'a Vec<u64>> {
+    let mut valid_tickets = Vec::new();
+    for ticket in all_tickets {
+        for value in ticket {
+            if rules.iter().any(|rule| rule.is_valid(value)) {
+                valid_tickets.push(ticket);
+            }
+        }
+    }
+    valid_tickets
+}
+
+/// Parses rules like "class: 1-3 or 5-7".
+pub fn parse_rules(raw_rules: &Record) -> Result<Vec<Rule>> {
+    let rule_re = Regex::new(r"^(?P<field>.*): (?P<low_range_lb>\d+)-(?P<low_range_ub>\d+) or (?P<high_range_lb>\d+)-(?P<high_range_ub>\d+)$").unwrap();
+    let mut rules = Vec::new();
+    for (i, raw_rule) in raw_rules.lines.iter().enumerate() {
+        let line_number = raw_rules.line + i;
+        let captures = rule_re.captures(raw_rule.as_str()).ok_or_else(|| {
+            Error::parse(
+                line_number,
+                raw_rule,
+                raw_rule,
+                "a rule like '<field>: 1-3 or 5-7'",
+            )
+        })?;
+        let to_range = |lb: &str, ub: &str| -> Result<RangeInclusive<u64>> {
+            let start = parse_at(
+                line_number,
+                raw_rule,
+                captures.name(lb).unwrap().as_str(),
+                "a number",
+            )?;
+            let end = parse_at(
+                line_number,
+                raw_rule,
+                captures.name(ub).unwrap().as_str(),
+                "a number",
+            )?;
+            Ok(start..=end)
+        };
+        rules.push(Rule {
+            field_name: String::from(captures.name("field").unwrap().as_str()),
+            low_range: to_range("low_range_lb", "low_range_ub")?,
+            high_range: to_range("high_range_lb", "high_range_ub")?,
+        });
+    }
+    Ok(rules)
+}
+
+#[test]
+fn test_part1() {
+    let input = Day16::default().parse_file("sample_input.txt").unwrap();
+    assert_eq!(input.rules.len(), 3);
+    assert_eq!(input.nearby_tickets.len(), 4);
+    assert_eq!(nearby_error_rate(&input).unwrap(), 71);
+}
+
+#[test]
+fn test_part2() {
+    let input = Day16::default().parse_file("sample_input_2.txt").unwrap();
+    let ordered_fields = get_ordered_fields(&input).unwrap();
+    assert_eq!(ordered_fields.len(), 3);
+    assert_eq!(ordered_fields.first().unwrap(), "row");
+    assert_eq!(ordered_fields.get(1).unwrap(), "class");
+    assert_eq!(ordered_fields.get(2).unwrap(), "seat");
+}
+
+#[cfg(test)]
+use aoc_common::{CancelToken, Solver};
+#[cfg(test)]
+use std::time::Duration;
+
+#[test]
+fn test_ambiguous_rules() {
+    // Either field fits either position, so neither is ever matched
+    let notes = "a: 0-1 or 2-3\nb: 0-1 or 2-3\n\nyour ticket:\n1,2\n\nnearby tickets:\n1,2";
+    let error = Day16::default().solve_str(notes, &[1, 2]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no answer: the rules fit 2 of the 2 positions to more than one field"
+    );
+}
+
+#[test]
+fn test_time_out() {
+    let notes = std::fs::read_to_string("sample_input_2.txt").unwrap();
+    let token = CancelToken::with_timeout(Duration::from_millis(0));
+    let error = token
+        .install(|| Day16::default().solve_str(&notes, &[1, 2]))
+        .unwrap_err();
+    assert_eq!(error.to_string(), "part 2 timed out after 0ns");
+}
+
+#[test]
+fn test_invalid_ticket() {
+    let record = Record {
+        line: 5,
+        lines: vec![String::from("your ticket:"), String::from("7,x,14")],
+    };
+    let error = ticket_section(Some(record), "your ticket:", 3).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:6:3: expected a field value, found \"x\""
+    );
+    assert!(ticket_section(None, "nearby tickets:", 3).is_err());
+
+    let notes = "a: 0-1 or 2-3\n\nyour ticket:\n1\n\nnearby tickets:\n1\n2,3";
+    let error = Day16::default().parse_str(notes).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:8:1: expected a ticket with 1 fields, found \"2,3\""
+    );
+}
+
+#[test]
+fn test_overflow() {
+    let notes = "a: 1-18446744073709551615 or 5-7\n\n\
+                 your ticket:\n4294967296\n\n\
+                 nearby tickets:\n0\n18446744073709551615";
+    let input = Day16::default().parse_str(notes).unwrap();
+    assert!(input.rules[0].is_valid(&u64::MAX));
+    assert_eq!(nearby_error_rate(&input).unwrap(), 0);
+
+    let notes = "a: 2-3 or 5-7\nb: 2-3 or 8-9\n\n\
+                 your ticket:\n4294967296,4294967296\n\n\
+                 nearby tickets:\n5,9\n18446744073709551615,1";
+    let input = Day16::default().parse_str(notes).unwrap();
+    let error = nearby_error_rate(&input).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "overflow: 18446744073709551615 + 1 doesn't fit"
+    );
+    let error = part_2(&input, "").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "overflow: 4294967296 * 4294967296 doesn't fit"
+    );
+}
@@ -0,0 +1,150 @@
+//! Day 2: Password Philosophy. Checks passwords against their policies.
+
+use aoc_common::{parse_at, read_lines, trace, Error, Result, Solution};
+use std::io::BufRead;
+
+/// A line of the password database: a policy and the password it applies to.
+#[derive(Debug)]
+pub struct PasswordEntry {
+    // The minimum count in part 1, and the first position (from 1) in part 2
+    pub first_number: usize,
+    // The maximum count in part 1, and the second position in part 2
+    pub second_number: usize,
+    pub required_char: char,
+    pub password: String,
+}
+
+impl PasswordEntry {
+    /// Parses a line like "1-3 a: abcde".
+    pub fn parse(line_number: usize, line: &str) -> Result<PasswordEntry> {
+        let parts: Vec<&str> = line.split(' ').collect();
+        if parts.len() != 3 {
+            return Err(Error::parse(
+                line_number,
+                line,
+                line,
+                "a line like \"1-3 a: abcde\"",
+            ));
+        }
+        let numbers: Vec<&str> = parts[0].split('-').collect();
+        if numbers.len() != 2 {
+            return Err(Error::parse(
+                line_number,
+                line,
+                parts[0],
+                "a range like \"1-3\"",
+            ));
+        }
+        let required_char = match parts[1]
+            .strip_suffix(':')
+            .map(|c| c.chars().collect::<Vec<char>>())
+        {
+            Some(chars) if chars.len() == 1 => chars[0],
+            _ => {
+                return Err(Error::parse(
+                    line_number,
+                    line,
+                    parts[1],
+                    "a letter followed by ':'",
+                ))
+            }
+        };
+        Ok(PasswordEntry {
+            first_number: parse_at(line_number, line, numbers[0], "a number")?,
+            second_number: parse_at(line_number, line, numbers[1], "a number")?,
+            required_char,
+            password: String::from(parts[2]),
+        })
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PasswordEntry>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<PasswordEntry>> {
+        read_lines(input)?
+            .iter()
+            .enumerate()
+            .map(|(i, line)| PasswordEntry::parse(i + 1, line))
+            .collect()
+    }
+
+    fn part1(&self, entries: &Vec<PasswordEntry>) -> Result<usize> {
+        Ok(entries.iter().filter(|e| is_valid_password(e)).count())
+    }
+
+    fn part2(&self, entries: &Vec<PasswordEntry>, _: &usize) -> Result<usize> {
+        Ok(entries
+            .iter()
+            .filter(|e| is_valid_password_part2(e))
+            .count())
+    }
+}
+
+/// Whether the password has the required character between the minimum and
+/// maximum number of times.
+pub fn is_valid_password(entry: &PasswordEntry) -> bool {
+    let min_repetitions = entry.first_number;
+    let max_repetitions = entry.second_number;
+    let required_char = entry.required_char;
+    let password = &entry.password;
+    trace!("{} is required {} to {} times", required_char, min_repetitions, max_repetitions);
+    let mut occurrences = 0;
+    for c in password.chars() {
+        if c == required_char {
+            occurrences += 1;
+        }
+    }
+    trace!("Found {} occurrences in {}", occurrences, password);
+    occurrences >= min_repetitions && occurrences <= max_repetitions
+}
+
+/// Whether the required character is at exactly one of the two positions.
+pub fn is_valid_password_part2(entry: &PasswordEntry) -> bool {
+    let first_position = entry.first_number;
+    let second_position = entry.second_number;
+    let required_char = entry.required_char;
+    let password = &entry.password;
+    trace!("{} is required at {} or {}", required_char, first_position, second_position);
+    let char_at = |position: usize| {
+        position
+            .checked_sub(1)
+            .and_then(|i| password.chars().nth(i))
+            .unwrap_or('_')
+    };
+    (char_at(first_position) == required_char) ^ (char_at(second_position) == required_char)
+}
+
+#[cfg(test)]
+fn entry(line: &str) -> PasswordEntry {
+    PasswordEntry::parse(1, line).unwrap()
+}
+
+#[test]
+fn test() {
+    assert!(is_valid_password(&entry("1-3 a: abcde")));
+    assert!(!is_valid_password(&entry("1-3 b: cdefg")));
+}
+
+#[test]
+fn test_part2() {
+    assert!(is_valid_password_part2(&entry("1-3 a: abcde")));
+    assert!(!is_valid_password_part2(&entry("1-3 b: cdefg")));
+    assert!(!is_valid_password_part2(&entry("2-9 c: ccccccccc")));
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = PasswordEntry::parse(4, "1-x a: abcde").err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "<input>:4:3: expected a number, found \"x\""
+    );
+    assert!(PasswordEntry::parse(1, "1-3 abcde").is_err());
+    assert!(PasswordEntry::parse(1, "13 a: abcde").is_err());
+    assert!(PasswordEntry::parse(1, "1-3 ab: abcde").is_err());
+}
@@ -0,0 +1,158 @@
+//! Day 3: Toboggan Trajectory. Counts the trees hit going down a slope.
+
+use aoc_common::{read_lines, Config, Error, Result, Solution};
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub struct Day3 {
+    // How many columns the map repeats every; defaults to the map's own width
+    pub width: Option<usize>,
+    pub slope: Slope,
+    // The slopes whose tree counts are multiplied together in part 2
+    pub slopes: Vec<Slope>,
+}
+
+impl Default for Day3 {
+    fn default() -> Day3 {
+        Day3 {
+            width: None,
+            slope: Slope { right: 3, down: 1 },
+            slopes: vec![
+                Slope { right: 1, down: 1 },
+                Slope { right: 3, down: 1 },
+                Slope { right: 5, down: 1 },
+                Slope { right: 7, down: 1 },
+                Slope { right: 1, down: 2 },
+            ],
+        }
+    }
+}
+
+/// Squares moved right and down on each step, written as `right,down`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Slope {
+    pub right: usize,
+    pub down: usize,
+}
+
+impl FromStr for Slope {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Slope, ()> {
+        let mut parts = s.splitn(2, ',');
+        let right = parts.next().unwrap_or_default().parse().map_err(|_| ())?;
+        let down = parts.next().unwrap_or_default().parse().map_err(|_| ())?;
+        match down {
+            0 => Err(()),
+            _ => Ok(Slope { right, down }),
+        }
+    }
+}
+
+impl Day3 {
+    fn width(&self, lines: &[Vec<char>]) -> Result<usize> {
+        let map_width = lines[0].len();
+        match self.width {
+            None => Ok(map_width),
+            Some(width) if width > 0 && width <= map_width => Ok(width),
+            Some(width) => Err(Error::NoAnswer(format!(
+                "a width of {} doesn't fit a map {} squares wide",
+                width, map_width
+            ))),
+        }
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Vec<Vec<char>>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Vec<char>>> {
+        let lines = read_lines(input)?;
+        let line_length = match lines.first() {
+            Some(line) if line.is_empty() => {
+                return Err(Error::parse(1, line, line, "a row of '.' and '#'"));
+            }
+            Some(line) => line.len(),
+            None => return Err(Error::NoAnswer(String::from("the map is empty"))),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            let invalid = line.char_indices().find(|(_, c)| *c != '.' && *c != '#');
+            if let Some((position, c)) = invalid {
+                let square = &line[position..position + c.len_utf8()];
+                return Err(Error::parse(i + 1, line, square, "'.' or '#'"));
+            }
+            if line.len() != line_length {
+                let expected = format!("a row {} squares wide", line_length);
+                return Err(Error::parse(i + 1, line, line, &expected));
+            }
+        }
+        Ok(lines.iter().map(|line| line.chars().collect()).collect())
+    }
+
+    fn part1(&self, lines: &Vec<Vec<char>>) -> Result<u32> {
+        let line_length = self.width(lines)?;
+        Ok(trees_hit(lines, line_length, self.slope.right, self.slope.down))
+    }
+
+    fn part2(&self, lines: &Vec<Vec<char>>, _: &u32) -> Result<u32> {
+        let line_length = self.width(lines)?;
+        Ok(self.slopes
+            .iter()
+            .map(|slope| trees_hit(lines, line_length, slope.right, slope.down))
+            .product())
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        let expected_slope = "a slope like 3,1";
+        self.width = config.get_opt("width", "a number of columns")?;
+        self.slope = config.get("slope", self.slope, expected_slope)?;
+        self.slopes = config.get_list("slopes", self.slopes.clone(), expected_slope)?;
+        Ok(())
+    }
+}
+
+/// Counts the trees (`#`) hit going down the map from the top left corner,
+/// where the map repeats every `line_length` columns.
+pub fn trees_hit(lines: &[Vec<char>], line_length: usize, right: usize, down: usize) -> u32 {
+    let mut row :usize = 0;
+    let mut column :usize = 0;
+    let row_count = lines.len();
+    let mut trees_hit = 0;
+    while row < row_count {
+        row += down;
+        column = (column + right) % line_length;
+        if row < row_count && lines[row][column] == '#' {
+            trees_hit += 1;
+        }
+    }
+    trees_hit
+}
+
+#[test]
+fn test() {
+    let sample_lines = Day3::default().parse_file("sample_input.txt").unwrap();
+    assert_eq!(7, trees_hit(&sample_lines, 66, 3, 1));
+}
+
+#[test]
+fn test_configure() {
+    let mut config = Config::parse("config.txt", "slope = 1,2\nslopes = 1,1 7,1").unwrap();
+    let day3 = Day3::default().with_config(&config).unwrap();
+    assert_eq!(day3.slope, Slope { right: 1, down: 2 });
+    assert_eq!(day3.slopes.len(), 2);
+
+    config.set("slope=1,0").unwrap();
+    assert!(Day3::default().with_config(&config).is_err());
+}
+
+
+#[test]
+fn test_parse_errors() {
+    let error = Day3::default().parse_str("..#\n.é.").unwrap_err();
+    assert_eq!(error.to_string(), "<input>:2:2: expected '.' or '#', found \"é\"");
+    let error = Day3::default().parse_str("\n\n\n").unwrap_err();
+    assert_eq!(error.to_string(), "<input>:1:1: expected a row of '.' and '#', found \"\"");
+    assert!(Day3::default().parse_str("..#\n.#").is_err());
+}
@@ -0,0 +1,178 @@
+//! Day 4: Passport Processing. Validates passport fields.
+
+use aoc_common::{read_records, Error, Record, Result, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::iter::FromIterator;
+
+/// A passport's fields, by their three-letter keys.
+#[derive(Debug)]
+pub struct Passport {
+    field_map: HashMap<String, String>,
+}
+
+impl Passport {
+    /// Parses a passport from `key:value` fields separated by spaces or lines.
+    pub fn from(record: &Record) -> Result<Passport> {
+        let mut field_map = HashMap::new();
+        for (i, line) in record.lines.iter().enumerate() {
+            let key_value_pairs = Vec::from_iter(line.split(' '));
+            for key_value_pair in key_value_pairs.iter() {
+                let split_kvp: Vec<&str> = Vec::from_iter(key_value_pair.split(':'));
+                if split_kvp.len() != 2 {
+                    return Err(Error::parse(
+                        record.line + i,
+                        line,
+                        key_value_pair,
+                        "a field like \"key:value\"",
+                    ));
+                }
+                let key = split_kvp[0];
+                let value = split_kvp[1];
+                field_map.insert(String::from(key), String::from(value));
+            }
+        }
+        Ok(Passport { field_map })
+    }
+
+    /// Whether every field but `cid` is present.
+    pub fn has_all_required_fields(&self) -> bool {
+        // Not sure how to make this more of a constant
+        let required_fields: HashSet<&str> = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"]
+            .into_iter()
+            .collect();
+        let field_map = &self.field_map;
+        let present_keys: HashSet<&str> = field_map.keys().map(String::as_str).collect();
+        required_fields.is_subset(&present_keys)
+    }
+
+    /// Whether every field that's present has a valid value.
+    pub fn present_fields_valid(&self) -> bool {
+        lazy_static! {
+            static ref VALID_ECLS: HashSet<String> =
+                vec!("amb", "blu", "brn", "gry", "grn", "hzl", "oth")
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+            static ref HCL_REGEX: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
+        }
+        let field_map = &self.field_map;
+        let mut is_valid = true;
+
+        for (key, value) in field_map {
+            is_valid = is_valid
+                && match key.as_str() {
+                    "byr" => value
+                        .parse::<i32>()
+                        .is_ok_and(|numeric_value| (1920..=2002).contains(&numeric_value)),
+                    "iyr" => value
+                        .parse::<i32>()
+                        .is_ok_and(|numeric_value| (2010..=2020).contains(&numeric_value)),
+                    "eyr" => value
+                        .parse::<i32>()
+                        .is_ok_and(|numeric_value| (2020..=2030).contains(&numeric_value)),
+                    "hgt" => {
+                        // Anything that isn't a number followed by a unit is an invalid height
+                        let numeric_value = value
+                            .chars()
+                            .filter(|c| c.is_numeric())
+                            .collect::<String>()
+                            .parse::<u32>()
+                            .unwrap_or(0);
+                        let mut result = false;
+                        if value.ends_with("in") {
+                            result = (59..=76).contains(&numeric_value);
+                        } else if value.ends_with("cm") {
+                            result = (150..=193).contains(&numeric_value);
+                        }
+                        result
+                    }
+                    "hcl" => HCL_REGEX.is_match(value),
+                    "ecl" => VALID_ECLS.contains(value),
+                    "pid" => value.chars().all(char::is_numeric) && value.len() == 9,
+                    _ => true,
+                }
+        }
+        is_valid
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Passport>> {
+        read_records(input)?.iter().map(Passport::from).collect()
+    }
+
+    fn part1(&self, passports: &Vec<Passport>) -> Result<usize> {
+        Ok(passports
+            .iter()
+            .filter(|p| p.has_all_required_fields())
+            .count())
+    }
+
+    fn part2(&self, passports: &Vec<Passport>, _: &usize) -> Result<usize> {
+        Ok(passports
+            .iter()
+            .filter(|p| p.has_all_required_fields() && p.present_fields_valid())
+            .count())
+    }
+}
+
+#[test]
+fn test_part1() {
+    let passports = Day4.parse_file("sample_input_1.txt").unwrap();
+    assert_eq!(
+        2,
+        passports
+            .iter()
+            .filter(|&p| p.has_all_required_fields())
+            .count()
+    );
+}
+
+#[test]
+fn test_part2() {
+    let invalid_passports = Day4.parse_file("sample_input_2.txt").unwrap();
+    assert_eq!(
+        0,
+        invalid_passports
+            .iter()
+            .filter(|&p| p.has_all_required_fields() && p.present_fields_valid())
+            .count()
+    );
+
+    let valid_passports = Day4.parse_file("sample_input_3.txt").unwrap();
+    assert_eq!(
+        4,
+        valid_passports
+            .iter()
+            .filter(|&p| p.has_all_required_fields() && p.present_fields_valid())
+            .count()
+    );
+}
+
+#[test]
+fn test_malformed_passport() {
+    let record = Record {
+        line: 3,
+        lines: vec![String::from("ecl:gry"), String::from("pid:1 byr")],
+    };
+    let error = Passport::from(&record).err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "<input>:4:7: expected a field like \"key:value\", found \"byr\""
+    );
+
+    let record = Record {
+        line: 1,
+        lines: vec![String::from("byr:19x0 hgt:tall")],
+    };
+    assert!(!Passport::from(&record).unwrap().present_fields_valid());
+}
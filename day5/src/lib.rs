@@ -0,0 +1,127 @@
+//! Day 5: Binary Boarding. Decodes boarding passes into seats.
+
+use aoc_common::{read_lines, Error, Result, Solution};
+use std::cmp::{Ord, Ordering};
+use std::io::BufRead;
+
+/// A seat on the plane, ordered by its ID.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Seat {
+    pub row: u8,
+    pub column: u8,
+    // row * 8 + column
+    pub id: u32,
+}
+
+impl Ord for Seat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl PartialOrd for Seat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Seat>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Seat>> {
+        let mut seats = Vec::new();
+        for (i, line) in read_lines(input)?.iter().enumerate() {
+            match get_seat(line.as_str()) {
+                Some(seat) => seats.push(seat),
+                None => {
+                    let expected = "7 of 'F' or 'B' followed by 3 of 'L' or 'R'";
+                    return Err(Error::parse(i + 1, line, line, expected));
+                }
+            }
+        }
+        if seats.is_empty() {
+            return Err(Error::NoAnswer(String::from(
+                "there are no boarding passes",
+            )));
+        }
+        seats.sort();
+        Ok(seats)
+    }
+
+    fn part1(&self, seats: &Vec<Seat>) -> Result<u32> {
+        Ok(seats[seats.len() - 1].id)
+    }
+
+    fn part2(&self, seats: &Vec<Seat>, _: &u32) -> Result<u32> {
+        let mut part_2_answer = 0;
+        let mut previous_seat_id = seats[0].id;
+        for seat in seats {
+            if seat.id - previous_seat_id == 2 {
+                part_2_answer = previous_seat_id + 1;
+            }
+            previous_seat_id = seat.id;
+        }
+        Ok(part_2_answer)
+    }
+}
+
+/// Decodes a boarding pass like "FBFBBFFRLR", returning None if it isn't in
+/// that format.
+pub fn get_seat(encoded: &str) -> Option<Seat> {
+    let is_valid = encoded.len() == 10
+        && encoded.chars().take(7).all(|c| c == 'F' || c == 'B')
+        && encoded.chars().skip(7).all(|c| c == 'L' || c == 'R');
+    if !is_valid {
+        return None;
+    }
+    let row_raw: String = encoded
+        .replace("F", "0")
+        .replace("B", "1")
+        .chars()
+        .take(7)
+        .collect();
+    let row = u8::from_str_radix(&row_raw, 2).ok()?;
+
+    let column_raw: String = encoded
+        .replace("L", "0")
+        .replace("R", "1")
+        .chars()
+        .skip(7)
+        .take(3)
+        .collect();
+    let column = u8::from_str_radix(&column_raw, 2).ok()?;
+    Some(Seat {
+        row,
+        column,
+        id: row as u32 * 8 + column as u32,
+    })
+}
+
+#[test]
+fn test_get_seat() {
+    let seat = get_seat("BFFFBBFRRR").unwrap();
+    assert_eq!(70, seat.row);
+    assert_eq!(7, seat.column);
+    assert_eq!(567, seat.id);
+
+    let seat2 = get_seat("FFFBBBFRRR").unwrap();
+    assert_eq!(14, seat2.row);
+    assert_eq!(7, seat2.column);
+    assert_eq!(119, seat2.id);
+
+    let seat3 = get_seat("BBFFBBFRLL").unwrap();
+    assert_eq!(102, seat3.row);
+    assert_eq!(4, seat3.column);
+    assert_eq!(820, seat3.id);
+}
+
+#[test]
+fn test_invalid_seat() {
+    assert!(get_seat("BFFFBBFRR").is_none());
+    assert!(get_seat("BFFFBBFRRRR").is_none());
+    assert!(get_seat("BFFFBBLRRR").is_none());
+}
@@ -0,0 +1,96 @@
+//! Day 6: Custom Customs. Counts the questions each group answered.
+
+use aoc_common::{read_records, Error, Record, Result, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+/// The questions answered "yes" by each person in a group.
+#[derive(Debug)]
+pub struct AnswerGroup(pub Vec<HashSet<char>>);
+
+impl AnswerGroup {
+    /// Parses a group with one line of questions, from a to z, per person.
+    pub fn from(record: &Record) -> Result<AnswerGroup> {
+        let mut answers: Vec<HashSet<char>> = Vec::new();
+        for (i, line) in record.lines.iter().enumerate() {
+            let invalid = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase());
+            if let Some((position, c)) = invalid {
+                let question = &line[position..position + c.len_utf8()];
+                return Err(Error::parse(
+                    record.line + i,
+                    line,
+                    question,
+                    "a question from a to z",
+                ));
+            }
+            let line_answers: HashSet<char> = line.chars().collect();
+            answers.push(line_answers);
+        }
+        Ok(AnswerGroup(answers))
+    }
+
+    /// How many questions anyone in the group answered.
+    pub fn distinct_answer_count(&self) -> u32 {
+        let mut distinct_answers: HashSet<char> = HashSet::new();
+        for answers in &self.0 {
+            distinct_answers.extend(answers);
+        }
+        distinct_answers.len() as u32
+    }
+
+    /// How many questions everyone in the group answered.
+    pub fn common_answer_count(&self) -> u32 {
+        let mut common_answers: HashSet<char> = HashSet::new();
+        for (i, answers) in self.0.iter().enumerate() {
+            if i == 0 {
+                common_answers.extend(answers);
+            } else {
+                common_answers = common_answers.intersection(answers).cloned().collect();
+            }
+        }
+        common_answers.len() as u32
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<AnswerGroup>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<AnswerGroup>> {
+        to_answer_groups(&read_records(input)?)
+    }
+
+    fn part1(&self, answer_groups: &Vec<AnswerGroup>) -> Result<u32> {
+        Ok(answer_groups
+            .iter()
+            .map(|a| a.distinct_answer_count())
+            .sum())
+    }
+
+    fn part2(&self, answer_groups: &Vec<AnswerGroup>, _: &u32) -> Result<u32> {
+        Ok(answer_groups.iter().map(|a| a.common_answer_count()).sum())
+    }
+}
+
+pub fn to_answer_groups(records: &[Record]) -> Result<Vec<AnswerGroup>> {
+    records.iter().map(AnswerGroup::from).collect()
+}
+
+#[test]
+fn test() {
+    let answer_groups = Day6.parse_file("sample_input_1.txt").unwrap();
+    assert_eq!(11, Day6.part1(&answer_groups).unwrap());
+    assert_eq!(6, Day6.part2(&answer_groups, &11).unwrap());
+}
+
+#[test]
+fn test_parse_errors() {
+    let error = Day6.parse_str("abc\naé").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:2:2: expected a question from a to z, found \"é\""
+    );
+}
@@ -0,0 +1,289 @@
+//! Day 7: Handy Haversacks. Works out which bags hold which.
+
+use aoc_common::{parse_at, read_lines, trace, Config, Error, Result, Solution};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::iter::FromIterator;
+
+/// A bag color and how many bags of each color it must contain.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Bag {
+    pub color: String,
+    pub allowed_bags: HashMap<String, u32>,
+}
+
+pub struct Day7 {
+    // The color of the bag to hold in part 1 and to fill in part 2
+    pub bag: String,
+}
+
+impl Default for Day7 {
+    fn default() -> Day7 {
+        Day7 {
+            bag: String::from("shiny gold"),
+        }
+    }
+}
+
+impl Solution for Day7 {
+    type Input = HashMap<String, Bag>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<HashMap<String, Bag>> {
+        to_bags(&read_lines(input)?)
+    }
+
+    fn part1(&self, bags: &HashMap<String, Bag>) -> Result<u32> {
+        count_possible_holders(&self.bag, bags)
+    }
+
+    fn part2(&self, bags: &HashMap<String, Bag>, _: &u32) -> Result<u32> {
+        let outer_bag = get_bag(&self.bag, bags)?;
+        contained_bag_count(outer_bag, bags)
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.bag = config.get("bag", self.bag.clone(), "a bag color")?;
+        Ok(())
+    }
+}
+
+/// Looks up the rule for a bag color.
+pub fn get_bag<'a>(color: &str, bags_by_name: &'a HashMap<String, Bag>) -> Result<&'a Bag> {
+    bags_by_name
+        .get(color)
+        .ok_or_else(|| Error::NoAnswer(format!("there is no rule for {} bags", color)))
+}
+
+/// Parses rules like "light red bags contain 1 bright white bag, 2 muted
+/// yellow bags.", keyed by color.
+pub fn to_bags(lines: &[String]) -> Result<HashMap<String, Bag>> {
+    let mut bags_by_name: HashMap<String, Bag> = HashMap::new();
+    let line_regex = Regex::new(r"(\d+ \w+ \w+)+").unwrap();
+    let count_and_type = Regex::new(r"^(?P<count>\d+) (?P<type>\w+ \w+)$").unwrap();
+    for (i, line) in lines.iter().enumerate() {
+        let split: Vec<&str> = Vec::from_iter(line.split(" contain "));
+        if split.len() != 2 {
+            return Err(Error::parse(
+                i + 1,
+                line,
+                line,
+                "a rule like '<color> bags contain <count> <color> bags'",
+            ));
+        }
+        let (color, contained) = (split[0], split[1]);
+        let mut allowed_bags: HashMap<String, u32> = HashMap::new();
+        for contained_bag_match in line_regex.find_iter(contained) {
+            for m2 in count_and_type.captures_iter(contained_bag_match.as_str()) {
+                allowed_bags.insert(
+                    String::from(&m2["type"]),
+                    parse_at(i + 1, line, &m2["count"], "a bag count")?,
+                );
+            }
+        }
+
+        let bag = Bag {
+            color: String::from(color.replace("bags", "").replace("bag", "").trim()),
+            allowed_bags,
+        };
+        let key = bag.color.clone();
+        bags_by_name.insert(key, bag);
+    }
+    Ok(bags_by_name)
+}
+
+/// Counts the bag colors that eventually contain a `bag_type` bag.
+pub fn count_possible_holders(bag_type: &str, bags_by_name: &HashMap<String, Bag>) -> Result<u32> {
+    let mut holder_count: u32 = 0;
+    for bag in bags_by_name.values() {
+        if can_hold_bag_type(bag_type, bag, bags_by_name)? {
+            holder_count += 1;
+        }
+    }
+    Ok(holder_count)
+}
+
+/// Whether `bag_to_check`, or any bag inside it, contains a `bag_type` bag.
+pub fn can_hold_bag_type(
+    bag_type: &str,
+    bag_to_check: &Bag,
+    bags_by_name: &HashMap<String, Bag>,
+) -> Result<bool> {
+    can_hold_along(bag_type, bag_to_check, bags_by_name, &mut HashSet::new())
+}
+
+fn can_hold_along<'a>(
+    bag_type: &str,
+    bag_to_check: &'a Bag,
+    bags_by_name: &'a HashMap<String, Bag>,
+    path: &mut HashSet<&'a str>,
+) -> Result<bool> {
+    if bag_to_check.allowed_bags.contains_key(bag_type) {
+        return Ok(true);
+    } else if bag_to_check.allowed_bags.is_empty() {
+        return Ok(false);
+    }
+    enter(&bag_to_check.color, path)?;
+    for contained_bag in bag_to_check.allowed_bags.keys() {
+        let next_bag_to_check = get_bag(contained_bag, bags_by_name)?;
+        if can_hold_along(bag_type, next_bag_to_check, bags_by_name, path)? {
+            return Ok(true);
+        }
+    }
+    path.remove(bag_to_check.color.as_str());
+    Ok(false)
+}
+
+/// Counts all the bags inside `bag_to_check`.
+pub fn contained_bag_count(bag_to_check: &Bag, bags_by_name: &HashMap<String, Bag>) -> Result<u32> {
+    count_along(bag_to_check, bags_by_name, &mut HashSet::new())
+}
+
+fn count_along<'a>(
+    bag_to_check: &'a Bag,
+    bags_by_name: &'a HashMap<String, Bag>,
+    path: &mut HashSet<&'a str>,
+) -> Result<u32> {
+    if bag_to_check.allowed_bags.is_empty() {
+        trace!("Base case: {} contains 0 bags", bag_to_check.color);
+        return Ok(0);
+    }
+    enter(&bag_to_check.color, path)?;
+    let mut answer: u32 = 0;
+    for (contained_bag, count) in &bag_to_check.allowed_bags {
+        let next_bag_to_check = get_bag(contained_bag, bags_by_name)?;
+        let inside = count_along(next_bag_to_check, bags_by_name, path)?;
+        answer = inside
+            .checked_add(1)
+            .and_then(|bags| bags.checked_mul(*count))
+            .and_then(|bags| bags.checked_add(answer))
+            .ok_or_else(|| {
+                Error::Overflow(format!("{} bags hold too many bags", bag_to_check.color))
+            })?;
+    }
+    path.remove(bag_to_check.color.as_str());
+    trace!("{} contains {} bags", bag_to_check.color, answer);
+    Ok(answer)
+}
+
+// Adds `color` to the bags being opened, failing if it's already one of them.
+fn enter<'a>(color: &'a str, path: &mut HashSet<&'a str>) -> Result<()> {
+    if path.insert(color) {
+        Ok(())
+    } else {
+        Err(Error::NoAnswer(format!(
+            "bag rules contain a cycle through {} bags",
+            color
+        )))
+    }
+}
+
+#[test]
+fn test() {
+    let bags = Day7::default().parse_file("sample_input.txt").unwrap();
+
+    for (_, bag) in bags {
+        println!("{} contains {}", bag.color, bag.allowed_bags.len());
+    }
+}
+
+#[test]
+fn test_part2() {
+    let bags = Day7::default().parse_file("sample_input.txt").unwrap();
+    let bag_name = String::from("shiny gold");
+    let shiny_gold_bag = bags.get(bag_name.as_str()).unwrap();
+
+    assert_eq!(126, contained_bag_count(shiny_gold_bag, &bags).unwrap());
+}
+
+#[test]
+fn test_missing_rule() {
+    let bags = Day7::default()
+        .parse_str("shiny gold bags contain 2 dark red bags.")
+        .unwrap();
+    let error = Day7::default().part2(&bags, &0).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no answer: there is no rule for dark red bags"
+    );
+
+    assert!(Day7::default()
+        .parse_str("shiny gold bags hold 2 dark red bags.")
+        .is_err());
+}
+
+#[test]
+fn test_cycle() {
+    let day = Day7::default();
+    let bags = day
+        .parse_str(
+            "shiny gold bags contain 1 dark red bag.\n\
+             dark red bags contain 1 shiny gold bag.\n\
+             faded blue bags contain 1 dark red bag.",
+        )
+        .unwrap();
+    let error = day.part2(&bags, &0).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no answer: bag rules contain a cycle through shiny gold bags"
+    );
+
+    let bags = day
+        .parse_str(
+            "light red bags contain 1 dark orange bag.\n\
+             dark orange bags contain 1 light red bag.\n\
+             shiny gold bags contain no other bags.",
+        )
+        .unwrap();
+    let error = day.part1(&bags).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("no answer: bag rules contain a cycle through"));
+}
+
+#[test]
+fn test_too_many_bags() {
+    let day = Day7::default();
+    let bags = day
+        .parse_str(
+            "shiny gold bags contain 65536 dark red bags.\n\
+             dark red bags contain 65536 faded blue bags.\n\
+             faded blue bags contain no other bags.",
+        )
+        .unwrap();
+    let error = day.part2(&bags, &0).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "overflow: shiny gold bags hold too many bags"
+    );
+}
+
+#[test]
+fn test_regex() {
+    let sample =
+        "contain 4 vibrant bronze bags, 5 bright bronze bags, 1 faded red bag, 4 clear tan bags.";
+    let re = Regex::new(r"\d+").unwrap();
+    let counts: Vec<u32> = re
+        .find_iter(sample)
+        .map(|m| m.as_str().parse::<u32>().unwrap())
+        .collect();
+    assert!(counts.contains(&4));
+    assert!(counts.contains(&5));
+    assert!(counts.contains(&1));
+}
+
+#[test]
+fn test_regex2() {
+    let sample =
+        "contain 4 vibrant bronze bags, 5 bright bronze bags, 1 faded red bag, 4 clear tan bags.";
+    let line_regex = Regex::new(r"(\d+ \w+ \w+)+").unwrap();
+    let count_and_type = Regex::new(r"^(?P<count>\d+) (?P<type>\w+ \w+)$").unwrap();
+    for m in line_regex.find_iter(sample) {
+        println!("Match: {}", m.as_str());
+        for m2 in count_and_type.captures_iter(m.as_str()) {
+            println!("Match2: {} / {}", &m2["count"], &m2["type"]);
+        }
+    }
+}
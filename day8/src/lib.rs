@@ -0,0 +1,239 @@
+//! Day 8: Handheld Halting. Runs and repairs the handheld's boot code.
+
+use aoc_common::{add, parse_at, read_lines, Error, Result, Solution};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::BufRead;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<Instruction>> {
+        to_program(&read_lines(input)?)
+    }
+
+    fn part1(&self, program: &Vec<Instruction>) -> Result<i32> {
+        Ok(run_program(program)?.0)
+    }
+
+    fn part2(&self, program: &Vec<Instruction>, _: &i32) -> Result<i32> {
+        run_part_2(program)
+    }
+}
+
+/// An operation of the handheld's boot code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    Acc,
+    Jmp,
+    Nop,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub op: Op,
+    pub arg: i32,
+}
+
+impl Instruction {
+    /// Parses a line like "jmp -3".
+    pub fn parse(line_number: usize, line: &str) -> Result<Instruction> {
+        let mut parts = line.splitn(2, ' ');
+        let op_text = parts.next().unwrap_or_default();
+        let op = match op_text {
+            "acc" => Op::Acc,
+            "jmp" => Op::Jmp,
+            "nop" => Op::Nop,
+            _ => return Err(Error::parse(line_number, line, op_text, "acc, jmp or nop")),
+        };
+        let arg_text = parts.next().unwrap_or_default();
+        let arg = parse_at(line_number, line, arg_text, "a signed number")?;
+        Ok(Instruction { op, arg })
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self.op {
+            Op::Acc => "acc",
+            Op::Jmp => "jmp",
+            Op::Nop => "nop",
+        };
+        write!(f, "{} {:+}", op, self.arg)
+    }
+}
+
+/// The handheld's state while it runs a program one instruction at a time.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Machine {
+    pub accumulator: i32,
+    // The next instruction to run
+    pub instruction_number: usize,
+    pub steps: usize,
+    // Set by a jump before the first instruction, which stops the program
+    pub jumped_before_start: bool,
+}
+
+impl Machine {
+    /// Runs the next instruction and returns it, or None once the program
+    /// has stopped by running off either end. Fails, without running the
+    /// instruction, if it would overflow the accumulator.
+    pub fn step(&mut self, program: &[Instruction]) -> Result<Option<Instruction>> {
+        if self.jumped_before_start {
+            return Ok(None);
+        }
+        let instruction = match program.get(self.instruction_number) {
+            Some(instruction) => *instruction,
+            None => return Ok(None),
+        };
+        match instruction.op {
+            Op::Acc => {
+                self.accumulator =
+                    self.accumulator
+                        .checked_add(instruction.arg)
+                        .ok_or_else(|| {
+                            Error::Overflow(format!(
+                                "instruction {} ({}) overflows the accumulator {}",
+                                self.instruction_number, instruction, self.accumulator
+                            ))
+                        })?;
+                self.instruction_number += 1;
+            }
+            Op::Jmp => match add(self.instruction_number, instruction.arg) {
+                Some(next) => self.instruction_number = next,
+                None => self.jumped_before_start = true,
+            },
+            Op::Nop => self.instruction_number += 1,
+        }
+        self.steps += 1;
+        Ok(Some(instruction))
+    }
+
+    /// Whether the program terminated normally, by running off its end.
+    pub fn terminated(&self, program: &[Instruction]) -> bool {
+        !self.jumped_before_start && self.instruction_number == program.len()
+    }
+}
+
+pub fn to_program(lines: &[String]) -> Result<Vec<Instruction>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| Instruction::parse(i + 1, line))
+        .collect()
+}
+
+/// Runs a program until it would repeat an instruction or runs off the end.
+/// Returns the accumulator value, and whether the program ran to completion.
+pub fn run_program(program: &[Instruction]) -> Result<(i32, bool)> {
+    let mut machine = Machine::default();
+    let mut executed_instructions: HashSet<usize> = HashSet::new();
+    while executed_instructions.insert(machine.instruction_number)
+        && machine.step(program)?.is_some()
+    {}
+    Ok((machine.accumulator, machine.terminated(program)))
+}
+
+/// Finds the accumulator value after the program terminates with one `jmp`
+/// swapped for a `nop` or the other way around.
+pub fn run_part_2(program: &[Instruction]) -> Result<i32> {
+    for (i, instruction) in program.iter().enumerate() {
+        if instruction.op != Op::Acc {
+            let change_to_try = copy_with_line_change(program, i);
+            let run_result = run_program(&change_to_try)?;
+            if run_result.1 {
+                return Ok(run_result.0);
+            }
+        }
+    }
+    Err(Error::NoAnswer(String::from(
+        "no single jmp/nop swap makes the program terminate",
+    )))
+}
+
+/// Copies the program with the `jmp` or `nop` at `line_to_change` swapped.
+pub fn copy_with_line_change(program: &[Instruction], line_to_change: usize) -> Vec<Instruction> {
+    let mut new_program = program.to_vec();
+    let changed = &mut new_program[line_to_change];
+    changed.op = match changed.op {
+        Op::Jmp => Op::Nop,
+        Op::Nop => Op::Jmp,
+        Op::Acc => Op::Acc,
+    };
+    new_program
+}
+
+#[test]
+fn test() {
+    let program = Day8.parse_file("sample_input.txt").unwrap();
+    let (part_1_answer, _) = run_program(&program).unwrap();
+    println!("PART1: {}", part_1_answer);
+}
+
+#[test]
+fn test_part2() {
+    let program = Day8.parse_file("sample_input.txt").unwrap();
+    let good_example = copy_with_line_change(&program, 7);
+    assert!(!run_program(&program).unwrap().1);
+    assert!(run_program(&good_example).unwrap().1);
+}
+
+#[test]
+fn test_machine() {
+    let program = Day8.parse_file("sample_input.txt").unwrap();
+    let mut machine = Machine::default();
+    let ran: Vec<String> = (0..4)
+        .filter_map(|_| machine.step(&program).unwrap())
+        .map(|i| i.to_string())
+        .collect();
+    assert_eq!(ran, vec!["nop +0", "acc +1", "jmp +4", "acc +1"]);
+    assert_eq!(
+        (
+            machine.accumulator,
+            machine.instruction_number,
+            machine.steps
+        ),
+        (2, 7, 4)
+    );
+
+    let program = Day8.parse_str("acc +2\njmp -5").unwrap();
+    let mut machine = Machine::default();
+    assert!(machine.step(&program).unwrap().is_some());
+    assert!(machine.step(&program).unwrap().is_some());
+    assert_eq!(machine.step(&program).unwrap(), None);
+    assert!(!machine.terminated(&program));
+    assert_eq!(run_program(&program).unwrap(), (2, false));
+}
+
+#[test]
+fn test_overflow() {
+    let program = Day8.parse_str("acc +2147483647\nacc +1").unwrap();
+    let error = Day8.part1(&program).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "overflow: instruction 1 (acc +1) overflows the accumulator 2147483647"
+    );
+
+    let mut machine = Machine::default();
+    assert!(machine.step(&program).unwrap().is_some());
+    assert!(machine.step(&program).is_err());
+    assert_eq!((machine.accumulator, machine.steps), (i32::MAX, 1));
+}
+
+#[test]
+fn test_invalid_instruction() {
+    let error = Day8.parse_str("nop +0\nmul +2").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:2:1: expected acc, jmp or nop, found \"mul\""
+    );
+    let error = Day8.parse_str("jmp x").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "<input>:1:5: expected a signed number, found \"x\""
+    );
+}
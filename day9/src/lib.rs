@@ -0,0 +1,113 @@
+//! Day 9: Encoding Error. Finds the weakness in the XMAS encryption.
+
+use aoc_common::{debug, read_numbers, Config, Error, Result, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::iter::FromIterator;
+
+pub struct Day9 {
+    // How many previous numbers each number must be the sum of two of
+    pub preamble: usize,
+}
+
+impl Default for Day9 {
+    fn default() -> Day9 {
+        Day9 { preamble: 25 }
+    }
+}
+
+impl Solution for Day9 {
+    type Input = Vec<u64>;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse<R: BufRead>(&self, input: R) -> Result<Vec<u64>> {
+        read_numbers(input)
+    }
+
+    fn part1(&self, numbers: &Vec<u64>) -> Result<u64> {
+        run_part1(numbers, self.preamble)
+    }
+
+    fn part2(&self, numbers: &Vec<u64>, invalid_number: &u64) -> Result<u64> {
+        run_part2(numbers, *invalid_number)
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.preamble = config.get("preamble", self.preamble, "a number")?;
+        Ok(())
+    }
+}
+
+/// Finds the first number that isn't the sum of two of the `preamble_length`
+/// numbers before it.
+pub fn run_part1(numbers: &[u64], preamble_length: usize) -> Result<u64> {
+    for (i, number) in numbers.iter().enumerate() {
+        if i < preamble_length {
+            continue;
+        }
+        let last_5_values = numbers[i - preamble_length..i].to_vec();
+        if !has_summing_pair(&last_5_values, *number) {
+            return Ok(*number);
+        }
+    }
+    Err(Error::NoAnswer(format!(
+        "every number is the sum of two of the {} before it",
+        preamble_length
+    )))
+}
+
+/// Finds a contiguous run of numbers adding up to `sum_to_number`, and returns
+/// the sum of its smallest and largest numbers.
+pub fn run_part2(numbers: &[u64], sum_to_number: u64) -> Result<u64> {
+    let mut current_window: Vec<u64> = Vec::new();
+    for number in numbers.iter() {
+        current_window.push(*number);
+        let mut current_sum: u64 = current_window.iter().sum();
+        while current_sum > sum_to_number {
+            let removed = current_window.drain(..1).next().unwrap_or(0);
+            current_sum -= removed;
+        }
+        if current_sum == sum_to_number {
+            debug!(
+                "Found {} numbers that sum to {}",
+                current_window.len(),
+                sum_to_number
+            );
+            let min = current_window.iter().min().cloned().unwrap_or(0);
+            let max = current_window.iter().max().cloned().unwrap_or(0);
+            return Ok(min + max);
+        }
+    }
+    Err(Error::NoAnswer(format!(
+        "no contiguous run of numbers sums to {}",
+        sum_to_number
+    )))
+}
+
+/// Whether two of the values add up to `sum_to_value`.
+pub fn has_summing_pair(values: &[u64], sum_to_value: u64) -> bool {
+    let value_set: HashSet<u64> = HashSet::from_iter(values.iter().cloned());
+    for e1 in values.iter() {
+        let e2 = sum_to_value.checked_sub(*e1);
+        if e2.is_some() && value_set.contains(&e2.unwrap()) {
+            return true;
+        }
+    }
+    false
+}
+
+#[test]
+fn test() {
+    let lines = Day9::default().parse_file("sample_input.txt").unwrap();
+    let part_1_answer = run_part1(&lines, 5).unwrap();
+    println!("PART1: {}", part_1_answer);
+    assert_eq!(part_1_answer, 127);
+}
+#[test]
+fn test_part2() {
+    let lines = Day9::default().parse_file("sample_input.txt").unwrap();
+    let answer = run_part2(&lines, 127).unwrap();
+    println!("PART2: {}", answer);
+    assert_eq!(answer, 62);
+}